@@ -290,23 +290,15 @@
         // When grid-column-start is omitted, if grid-row-start is a <custom-ident>, all four
         // longhands are set to that value. Otherwise, it is set to auto."
         fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result where W: fmt::Write {
-            self.grid_row_start.to_css(dest)?;
-            let mut trailing_values = 3;
-            if self.grid_column_start.can_omit(self.grid_column_end) {
-                trailing_values -= 1;
-                if self.grid_row_start.can_omit(self.grid_row_end) {
-                    trailing_values -= 1;
-                    if self.grid_row_start.can_omit(self.grid_column_start) {
-                        trailing_values -= 1;
-                    }
-                }
-            }
-            let values = [&self.grid_column_start, &self.grid_row_end, &self.grid_column_end];
-            for value in values.iter().take(trailing_values) {
-                dest.write_str(" / ")?;
-                value.to_css(dest)?;
-            }
-            Ok(())
+            use crate::values::generics::grid::serialize_grid_area;
+
+            serialize_grid_area(
+                self.grid_row_start,
+                self.grid_column_start,
+                self.grid_row_end,
+                self.grid_column_end,
+                dest,
+            )
         }
     }
 </%helpers:shorthand>
@@ -13,9 +13,10 @@
 use super::{Animate, Procedure, ToAnimatedZero};
 use crate::values::computed::Integer;
 use crate::values::computed::LengthPercentage;
-use crate::values::computed::{GridTemplateComponent, TrackList, TrackSize};
+use crate::values::computed::{GridLine, GridTemplateComponent, TrackList, TrackSize};
 use crate::values::distance::{ComputeSquaredDistance, SquaredDistance};
 use crate::values::generics::grid as generics;
+use crate::values::generics::grid::{MAX_GRID_LINE, MIN_GRID_LINE};
 
 fn discrete<T: Clone>(from: &T, to: &T, procedure: Procedure) -> Result<T, ()> {
     if let Procedure::Interpolate { progress } = procedure {
@@ -147,6 +148,68 @@ impl Animate for TrackList {
     }
 }
 
+impl Animate for GridLine {
+    // https://drafts.csswg.org/css-grid/#grid-placement-property
+    // There's no interpolation defined for grid lines by spec. A span and a
+    // non-span can't be animated at all; lines with different idents, or
+    // with integers on opposite sides of zero, animate discretely; otherwise
+    // the integers are interpolated numerically, re-clamping the result the
+    // same way the parser does.
+    fn animate(&self, other: &Self, procedure: Procedure) -> Result<Self, ()> {
+        // A span can never be interpolated with a non-span.
+        if self.is_span != other.is_span {
+            return Err(());
+        }
+
+        if self.ident != other.ident || (self.line_num < 0) != (other.line_num < 0) {
+            return discrete(self, other, procedure);
+        }
+
+        let line_num = animate_with_discrete_fallback(&self.line_num, &other.line_num, procedure)?
+            .max(MIN_GRID_LINE)
+            .min(MAX_GRID_LINE);
+
+        Ok(GridLine {
+            ident: self.ident.clone(),
+            line_num,
+            is_span: self.is_span,
+        })
+    }
+}
+
+impl Animate for GridTemplateComponent {
+    fn animate(&self, other: &Self, procedure: Procedure) -> Result<Self, ()> {
+        match (self, other) {
+            (&generics::GridTemplateComponent::None, &generics::GridTemplateComponent::None) => {
+                Ok(generics::GridTemplateComponent::None)
+            },
+            (
+                &generics::GridTemplateComponent::TrackList(ref from),
+                &generics::GridTemplateComponent::TrackList(ref to),
+            ) => Ok(generics::GridTemplateComponent::TrackList(Box::new(
+                from.animate(to, procedure)?,
+            ))),
+            // `<line-name-list>` doesn't have a well-defined interpolation (and
+            // `subgrid` doesn't carry a `<track-list>` to fall back on), so a
+            // `Subgrid` on either side just flips discretely instead of
+            // erroring out, like any other non-interpolable value.
+            // TODO: Revisit once subgrid is addressed in the [grid-2] spec.
+            //
+            // `Masonry` doesn't carry a `<track-list>` either, so it's
+            // non-interpolable for the same reason and gets the same
+            // discrete treatment, whether paired with itself or with any
+            // other keyword.
+            (&generics::GridTemplateComponent::Subgrid(..), _) |
+            (_, &generics::GridTemplateComponent::Subgrid(..)) |
+            (&generics::GridTemplateComponent::Masonry, _) |
+            (_, &generics::GridTemplateComponent::Masonry) => {
+                discrete(self, other, procedure)
+            },
+            (_, _) => Err(()),
+        }
+    }
+}
+
 impl ComputeSquaredDistance for GridTemplateComponent {
     #[inline]
     fn compute_squared_distance(&self, _other: &Self) -> Result<SquaredDistance, ()> {
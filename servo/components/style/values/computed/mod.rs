@@ -10,6 +10,12 @@ use super::generics::grid::GridTemplateComponent as GenericGridTemplateComponent
 use super::generics::grid::ImplicitGridTracks as GenericImplicitGridTracks;
 use super::generics::grid::{GenericGridLine, GenericTrackBreadth};
 use super::generics::grid::{GenericTrackSize, TrackList as GenericTrackList};
+use super::generics::grid::{LineNameListValue, RepeatCount, TrackListValue};
+use super::generics::grid::LineNameList as GenericLineNameList;
+use super::generics::grid::NameRepeat as GenericNameRepeat;
+use super::generics::grid::TrackEdit as GenericTrackEdit;
+use super::generics::grid::TrackRepeat as GenericTrackRepeat;
+use super::CustomIdent;
 use super::generics::transform::IsParallelTo;
 use super::generics::{self, GreaterThanOrEqualToOne, NonNegative, ZeroToOne};
 use super::specified;
@@ -35,6 +41,7 @@ use std::cell::RefCell;
 use std::cmp;
 use std::f32;
 use std::ops::{Add, Sub};
+use style_traits::ToCss;
 
 #[cfg(feature = "gecko")]
 pub use self::align::{
@@ -999,12 +1006,319 @@ pub type ImplicitGridTracks = GenericImplicitGridTracks<TrackSize>;
 /// (could also be `<auto-track-list>` or `<explicit-track-list>`)
 pub type TrackList = GenericTrackList<LengthPercentage, Integer>;
 
+/// The computed value of a grid `<track-repeat>`.
+pub type TrackRepeat = GenericTrackRepeat<LengthPercentage, Integer>;
+
+/// A single edit needed to turn one expanded computed `<track-list>` into
+/// another, as produced by `TrackList::diff`.
+pub type TrackEdit = GenericTrackEdit<LengthPercentage>;
+
+/// The computed value of a subgrid `<name-repeat>`.
+pub type NameRepeat = GenericNameRepeat<Integer>;
+
+/// The computed value of a subgrid `<line-name-list>`.
+pub type LineNameList = GenericLineNameList<Integer>;
+
 /// The computed value of a `<grid-line>`.
 pub type GridLine = GenericGridLine<Integer>;
 
 /// `<grid-template-rows> | <grid-template-columns>`
 pub type GridTemplateComponent = GenericGridTemplateComponent<LengthPercentage, Integer>;
 
+/// A flattened, DevTools-inspector-friendly snapshot of a computed
+/// `<grid-template-rows>` or `<grid-template-columns>`, with any
+/// `repeat()` (including `<auto-repeat>`) expanded into its individual
+/// tracks. This is the payload consumed by the DevTools grid highlighter.
+#[derive(Debug, Default, PartialEq)]
+pub struct DevToolsGridTemplate {
+    /// The serialized `<track-size>` of each expanded track, in order.
+    pub track_sizes: Vec<String>,
+    /// The `<line-names>` before each track, plus one final trailing entry
+    /// for the names after the last track.
+    pub line_names: Vec<Vec<String>>,
+}
+
+impl TrackBreadth {
+    /// Clamps this `<track-breadth>` to be non-negative, canonicalizing an
+    /// out-of-range `<length-percentage>` or `fr` produced by animation or
+    /// `calc()` into a valid value.
+    pub fn clamp_non_negative(self) -> Self {
+        match self {
+            TrackBreadth::Breadth(lp) => TrackBreadth::Breadth(lp.clamp_to_non_negative()),
+            TrackBreadth::Fr(fr) => TrackBreadth::Fr(fr.max(0.)),
+            other => other,
+        }
+    }
+}
+
+impl TrackRepeat {
+    /// Materializes a fixed-count `repeat()` into an explicit sequence of
+    /// `<track-size>`s and the `<line-names>` between them, merging the
+    /// repeat's own boundary line names across the repetition seam so the
+    /// "N tracks ↔ N+1 name lists" invariant holds in the flattened output.
+    ///
+    /// Returns `None` for `auto-fill`/`auto-fit`, whose count isn't known
+    /// until layout. The total track count is clamped to `MAX_GRID_LINE`,
+    /// matching the grid spec's overlarge-grid handling.
+    ///
+    /// This is distinct from `TrackRepeat::expand` (defined generically in
+    /// `generics::grid` alongside `resolve_tracks`, which uses it), which
+    /// also handles `auto-fill`/`auto-fit` given an
+    /// externally-resolved `auto_repeat_count`; this one is for callers that
+    /// specifically want the fixed-count-only behavior with the
+    /// seam-merged `<line-names>`.
+    pub fn expand_fixed(&self) -> Option<(Vec<TrackSize>, Vec<crate::OwnedSlice<CustomIdent>>)> {
+        let count = *self.count.as_number()? as usize;
+
+        let per_track = self.track_sizes.len();
+        let count = count.min(if per_track == 0 {
+            usize::MAX
+        } else {
+            (super::generics::grid::MAX_GRID_LINE as usize) / per_track
+        });
+
+        let mut track_sizes = Vec::with_capacity(count * per_track);
+        let mut line_names = Vec::with_capacity(count * per_track + 1);
+
+        let trailing = self.line_names.get(per_track).cloned().unwrap_or_default();
+        for i in 0..count {
+            for (j, size) in self.track_sizes.iter().enumerate() {
+                let names = self.line_names.get(j).cloned().unwrap_or_default();
+                if i > 0 && j == 0 {
+                    // Merge the last repetition's trailing names with this
+                    // repetition's leading names at the seam.
+                    let merged: Vec<_> = trailing.iter().cloned().chain(names.iter().cloned()).collect();
+                    line_names.push(merged.into());
+                } else {
+                    line_names.push(names);
+                }
+                track_sizes.push(size.clone());
+            }
+        }
+        line_names.push(trailing);
+
+        Some((track_sizes, line_names))
+    }
+}
+
+impl NameRepeat {
+    /// Materializes a fixed-count `<name-repeat>` into its concrete sequence
+    /// of `<line-names>` lists.
+    ///
+    /// Returns `None` for `auto-fill`, whose count is resolved by the
+    /// containing `<line-name-list>` at layout time. The total number of
+    /// line-name lists produced is clamped to `MAX_GRID_LINE`, matching the
+    /// grid spec's overlarge-grid handling.
+    pub fn expand(&self) -> Option<Vec<crate::OwnedSlice<CustomIdent>>> {
+        let count = *self.count.as_number()? as usize;
+
+        let per_repetition = self.line_names.len();
+        let count = count.min(if per_repetition == 0 {
+            usize::MAX
+        } else {
+            (super::generics::grid::MAX_GRID_LINE as usize) / per_repetition
+        });
+
+        let mut line_names = Vec::with_capacity(count * per_repetition);
+        for _ in 0..count {
+            line_names.extend(self.line_names.iter().cloned());
+        }
+
+        Some(line_names)
+    }
+}
+
+impl LineNameList {
+    /// Resolves the `<custom-ident>` line names declared at the given
+    /// (zero-based) position of this subgrid's `<line-name-list>`,
+    /// expanding the (at most one) `repeat(auto-fill, ...)` using the actual
+    /// repetition count the container resolved at layout time.
+    ///
+    /// Returns `None` if `line_index` falls outside every declared and
+    /// expanded position.
+    pub fn resolved_names_at(
+        &self,
+        line_index: usize,
+        auto_fill_repetitions: usize,
+    ) -> Option<Vec<CustomIdent>> {
+        let mut position = 0usize;
+
+        for value in self.line_names.iter() {
+            match *value {
+                LineNameListValue::LineNames(ref names) => {
+                    if position == line_index {
+                        return Some(names.to_vec());
+                    }
+                    position += 1;
+                },
+                LineNameListValue::Repeat(ref repeat) => {
+                    let repetitions = if repeat.is_auto_fill() {
+                        auto_fill_repetitions
+                    } else {
+                        repeat.count.as_number().map_or(0, |&n| n as usize)
+                    };
+                    let per_repetition = repeat.line_names.len();
+                    let total = repetitions * per_repetition;
+
+                    if per_repetition != 0 && line_index < position + total {
+                        let offset = (line_index - position) % per_repetition;
+                        return Some(repeat.line_names[offset].to_vec());
+                    }
+                    position += total;
+                },
+            }
+        }
+
+        None
+    }
+}
+
+impl TrackSize {
+    /// Clamps all the `<track-breadth>` values of this `<track-size>` to be
+    /// non-negative. See `TrackBreadth::clamp_non_negative`.
+    pub fn clamp_non_negative(self) -> Self {
+        match self {
+            TrackSize::Breadth(breadth) => TrackSize::Breadth(breadth.clamp_non_negative()),
+            TrackSize::Minmax(min, max) => {
+                TrackSize::Minmax(min.clamp_non_negative(), max.clamp_non_negative())
+            },
+            TrackSize::FitContent(breadth) => TrackSize::FitContent(breadth.clamp_non_negative()),
+        }
+    }
+}
+
+/// A single problem found by `GridTemplateComponent::validate`.
+#[derive(Debug, PartialEq)]
+pub enum GridTemplateDiagnostic {
+    /// A `<track-list>` doesn't have exactly one more `<line-names>` slot
+    /// than it has tracks.
+    WrongLineNameCount {
+        /// The number of `<line-names>` slots found.
+        found: usize,
+        /// The number of `<line-names>` slots expected, i.e. `tracks + 1`.
+        expected: usize,
+    },
+    /// More than one `<auto-repeat>` was found in a `<track-list>`. Only
+    /// `auto_repeat_index` is tracked, so extra ones are reported by index
+    /// among the repeat values.
+    MultipleAutoRepeats {
+        /// The index, within `values`, of the extra auto-repeat.
+        index: usize,
+    },
+    /// A track inside an `<auto-repeat>` isn't a fixed size
+    /// (`TrackSize::is_fixed` is false), e.g. it uses `fr`, `min-content`,
+    /// `max-content`, or `auto`.
+    AutoRepeatTrackNotFixedSize {
+        /// The index of the offending track within the repeat's track list.
+        index: usize,
+    },
+    /// A `minmax()` track size uses `fr` as its minimum, which the spec
+    /// disallows.
+    FrAsMinmaxMin {
+        /// The index of the offending track within `values`.
+        index: usize,
+    },
+    /// A `subgrid <line-name-list>` repeats `auto-fill` more than once.
+    MultipleAutoFillNameRepeats {
+        /// The index, within the line name list, of the extra `auto-fill` repeat.
+        index: usize,
+    },
+}
+
+impl GridTemplateComponent {
+    /// Checks all the invariants and spec constraints that
+    /// `GridTemplateComponent::parse` doesn't already enforce structurally,
+    /// returning one diagnostic per violation found. Used by authoring
+    /// tools that want a comprehensive correctness report without
+    /// re-implementing each rule.
+    pub fn validate(&self) -> Vec<GridTemplateDiagnostic> {
+        let mut diagnostics = vec![];
+
+        match *self {
+            GridTemplateComponent::TrackList(ref track_list) => {
+                let expected = track_list.values.len() + 1;
+                if track_list.line_names.len() != expected {
+                    diagnostics.push(GridTemplateDiagnostic::WrongLineNameCount {
+                        found: track_list.line_names.len(),
+                        expected,
+                    });
+                }
+
+                let mut seen_auto_repeat = false;
+                for (index, value) in track_list.values.iter().enumerate() {
+                    let repeat = match *value {
+                        TrackListValue::TrackRepeat(ref repeat) => repeat,
+                        TrackListValue::TrackSize(ref size) => {
+                            if let TrackSize::Minmax(TrackBreadth::Fr(_), _) = *size {
+                                diagnostics.push(GridTemplateDiagnostic::FrAsMinmaxMin { index });
+                            }
+                            continue;
+                        },
+                    };
+
+                    let is_auto = matches!(
+                        repeat.count,
+                        RepeatCount::AutoFill | RepeatCount::AutoFit
+                    );
+                    if is_auto {
+                        if seen_auto_repeat {
+                            diagnostics
+                                .push(GridTemplateDiagnostic::MultipleAutoRepeats { index });
+                        }
+                        seen_auto_repeat = true;
+
+                        for (track_index, size) in repeat.track_sizes.iter().enumerate() {
+                            if !size.is_fixed() {
+                                diagnostics.push(
+                                    GridTemplateDiagnostic::AutoRepeatTrackNotFixedSize {
+                                        index: track_index,
+                                    },
+                                );
+                            }
+                        }
+                    }
+
+                    for size in repeat.track_sizes.iter() {
+                        if let TrackSize::Minmax(TrackBreadth::Fr(_), _) = *size {
+                            diagnostics.push(GridTemplateDiagnostic::FrAsMinmaxMin { index });
+                        }
+                    }
+                }
+            },
+            GridTemplateComponent::Subgrid(ref line_name_list) => {
+                let mut seen_auto_fill = false;
+                for (index, value) in line_name_list.line_names.iter().enumerate() {
+                    if let LineNameListValue::Repeat(ref repeat) = *value {
+                        if repeat.is_auto_fill() {
+                            if seen_auto_fill {
+                                diagnostics.push(
+                                    GridTemplateDiagnostic::MultipleAutoFillNameRepeats { index },
+                                );
+                            }
+                            seen_auto_fill = true;
+                        }
+                    }
+                }
+            },
+            GridTemplateComponent::None | GridTemplateComponent::Masonry => {},
+        }
+
+        diagnostics
+    }
+}
+
+impl TrackSize {
+    /// Returns whether this and `other` serialize identically, i.e. they're
+    /// equal in CSS-canonical form. This is looser than `PartialEq`, which
+    /// compares the internal representation: `minmax(auto, 1fr)` and `1fr`
+    /// are `css_eq` even though one is a `Minmax` variant and the other a
+    /// bare `Breadth`, because `to_css` collapses the former into the
+    /// latter's spelling.
+    pub fn css_eq(&self, other: &Self) -> bool {
+        self.to_css_string() == other.to_css_string()
+    }
+}
+
 impl ClipRect {
     /// Given a border box, resolves the clip rect against the border box
     /// in the same space the border box is in
@@ -1030,3 +1344,521 @@ impl ClipRect {
         Rect::new(clip_origin, clip_size).translate(border_box.origin.to_vector())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_size_clamp_non_negative() {
+        let min = TrackBreadth::Breadth(LengthPercentage::new_length(Length::new(-10.)));
+        let max = TrackBreadth::Fr(-1.);
+        let size = TrackSize::Minmax(min, max).clamp_non_negative();
+
+        match size {
+            TrackSize::Minmax(TrackBreadth::Breadth(lp), TrackBreadth::Fr(fr)) => {
+                assert_eq!(lp, LengthPercentage::new_length(Length::new(0.)));
+                assert_eq!(fr, 0.);
+            },
+            _ => panic!("expected a Minmax track size"),
+        }
+    }
+
+    #[test]
+    fn grid_template_component_validate_wrong_line_name_count() {
+        let track_list = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![TrackListValue::TrackSize(TrackSize::Breadth(
+                TrackBreadth::Auto,
+            ))]
+            .into(),
+            // Should have 2 slots (values.len() + 1), only has 1.
+            line_names: vec![vec![].into()].into(),
+        };
+        let component = GridTemplateComponent::TrackList(Box::new(track_list));
+
+        assert_eq!(
+            component.validate(),
+            vec![GridTemplateDiagnostic::WrongLineNameCount {
+                found: 1,
+                expected: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn grid_template_component_validate_auto_repeat_not_fixed_size() {
+        use super::generics::grid::GenericTrackRepeat;
+
+        let track_list = TrackList {
+            auto_repeat_index: 0,
+            values: vec![TrackListValue::TrackRepeat(GenericTrackRepeat {
+                count: RepeatCount::AutoFill,
+                line_names: vec![vec![].into(), vec![].into()].into(),
+                track_sizes: vec![TrackSize::Breadth(TrackBreadth::Fr(1.))].into(),
+            })]
+            .into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        };
+        let component = GridTemplateComponent::TrackList(Box::new(track_list));
+
+        assert_eq!(
+            component.validate(),
+            vec![GridTemplateDiagnostic::AutoRepeatTrackNotFixedSize { index: 0 }]
+        );
+    }
+
+    #[test]
+    fn grid_template_component_to_devtools_expands_repeat() {
+        use super::generics::grid::{GenericTrackRepeat, RepeatCount, TrackListValue};
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let track_list = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![
+                TrackListValue::TrackSize(TrackSize::Breadth(TrackBreadth::Auto)),
+                TrackListValue::TrackRepeat(GenericTrackRepeat {
+                    count: RepeatCount::Number(2),
+                    line_names: vec![vec![ident("a")].into(), vec![].into()].into(),
+                    track_sizes: vec![TrackSize::Breadth(TrackBreadth::Breadth(
+                        LengthPercentage::new_length(Length::new(10.)),
+                    ))]
+                    .into(),
+                }),
+            ]
+            .into(),
+            line_names: vec![vec![].into(), vec![].into(), vec![ident("z")].into()].into(),
+        };
+
+        let component = GridTemplateComponent::TrackList(Box::new(track_list));
+        let devtools = component.to_devtools(0);
+
+        assert_eq!(devtools.track_sizes, vec!["auto", "10px", "10px"]);
+        assert_eq!(devtools.line_names.last().unwrap(), &vec!["z".to_string()]);
+        assert!(devtools
+            .line_names
+            .iter()
+            .any(|names| names == &vec!["a".to_string()]));
+    }
+
+    #[test]
+    fn grid_template_component_resolve_tracks_mixes_fixed_and_auto_repeat() {
+        use super::generics::grid::{GenericTrackRepeat, RepeatCount, TrackListValue};
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let track_list = TrackList {
+            auto_repeat_index: 1,
+            values: vec![
+                TrackListValue::TrackRepeat(GenericTrackRepeat {
+                    count: RepeatCount::Number(2),
+                    line_names: vec![vec![ident("a")].into(), vec![].into()].into(),
+                    track_sizes: vec![TrackSize::Breadth(TrackBreadth::Breadth(
+                        LengthPercentage::new_length(Length::new(10.)),
+                    ))]
+                    .into(),
+                }),
+                TrackListValue::TrackRepeat(GenericTrackRepeat {
+                    count: RepeatCount::AutoFill,
+                    line_names: vec![vec![].into(), vec![].into()].into(),
+                    track_sizes: vec![TrackSize::Breadth(TrackBreadth::Fr(1.))].into(),
+                }),
+            ]
+            .into(),
+            line_names: vec![vec![].into(), vec![].into(), vec![].into()].into(),
+        };
+
+        let component = GridTemplateComponent::TrackList(Box::new(track_list));
+        let resolved = component.resolve_tracks(3);
+
+        // The fixed `repeat(2, ...)` expands to 2 tracks, each preceded by
+        // `[a]`; the `<auto-repeat>` at index 1 expands to 3 (`auto_repeat_count`)
+        // copies of its `1fr` track.
+        assert_eq!(resolved.len(), 5);
+        assert_eq!(resolved[0], (vec![ident("a")], TrackSize::Breadth(TrackBreadth::Breadth(
+            LengthPercentage::new_length(Length::new(10.)),
+        ))));
+        assert_eq!(resolved[1].0, vec![ident("a")]);
+        for (names, size) in &resolved[2..] {
+            assert!(names.is_empty());
+            assert_eq!(*size, TrackSize::Breadth(TrackBreadth::Fr(1.)));
+        }
+    }
+
+    #[test]
+    fn track_list_enumerate_lines_expands_auto_repeat() {
+        use super::generics::grid::{GenericTrackRepeat, RepeatCount, TrackListValue};
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+
+        // `[a] repeat(auto-fill, [b] 1fr) [z]`, with auto-fill expanded twice.
+        let track_list = TrackList {
+            auto_repeat_index: 0,
+            values: vec![TrackListValue::TrackRepeat(GenericTrackRepeat {
+                count: RepeatCount::AutoFill,
+                line_names: vec![vec![ident("b")].into(), vec![].into()].into(),
+                track_sizes: vec![TrackSize::Breadth(TrackBreadth::Fr(1.))].into(),
+            })]
+            .into(),
+            line_names: vec![vec![ident("a")].into(), vec![ident("z")].into()].into(),
+        };
+
+        let lines = track_list.enumerate_lines(2);
+
+        // Two repeated tracks means 3 lines (0, 1, 2), each of which is named
+        // here: `[a]` before the first `[b]`, `[b]` again before the second
+        // repetition, and `[z]` (the track list's own trailing name) after it.
+        assert_eq!(
+            lines,
+            vec![
+                (0, vec![ident("a")]),
+                (0, vec![ident("b")]),
+                (1, vec![ident("b")]),
+                (2, vec![ident("z")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn track_size_css_eq_collapses_minmax() {
+        let fr = TrackSize::Breadth(TrackBreadth::Fr(1.));
+        let minmax = TrackSize::Minmax(TrackBreadth::Auto, TrackBreadth::Fr(1.));
+        assert!(fr.css_eq(&minmax));
+
+        let other_fr = TrackSize::Breadth(TrackBreadth::Fr(2.));
+        assert!(!fr.css_eq(&other_fr));
+    }
+
+    #[test]
+    fn track_size_minmax_serialization() {
+        // minmax(auto, <flex>) collapses to the bare flex value, to match Gecko.
+        assert_eq!(
+            TrackSize::Minmax(TrackBreadth::Auto, TrackBreadth::Fr(1.)).to_css_string(),
+            "1fr"
+        );
+
+        // All other minmax() shapes serialize verbatim.
+        assert_eq!(
+            TrackSize::Minmax(TrackBreadth::Auto, TrackBreadth::Auto).to_css_string(),
+            "minmax(auto, auto)"
+        );
+        assert_eq!(
+            TrackSize::Minmax(TrackBreadth::MinContent, TrackBreadth::MaxContent).to_css_string(),
+            "minmax(min-content, max-content)"
+        );
+        assert_eq!(
+            TrackSize::Minmax(
+                TrackBreadth::Breadth(LengthPercentage::new_length(Length::new(100.))),
+                TrackBreadth::Fr(1.),
+            )
+            .to_css_string(),
+            "minmax(100px, 1fr)"
+        );
+    }
+
+    #[test]
+    fn track_repeat_expand() {
+        use super::generics::grid::GenericTrackRepeat;
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let repeat = GenericTrackRepeat {
+            count: RepeatCount::Number(2),
+            line_names: vec![vec![ident("a")].into(), vec![ident("b")].into()].into(),
+            track_sizes: vec![TrackSize::Breadth(TrackBreadth::Breadth(
+                LengthPercentage::new_length(Length::new(10.)),
+            ))]
+            .into(),
+        };
+
+        let (track_sizes, line_names) = repeat.expand_fixed().unwrap();
+
+        assert_eq!(
+            track_sizes,
+            vec![
+                TrackSize::Breadth(TrackBreadth::Breadth(LengthPercentage::new_length(
+                    Length::new(10.)
+                ))),
+                TrackSize::Breadth(TrackBreadth::Breadth(LengthPercentage::new_length(
+                    Length::new(10.)
+                ))),
+            ]
+        );
+        assert_eq!(
+            line_names,
+            vec![
+                vec![ident("a")].into(),
+                vec![ident("b"), ident("a")].into(),
+                vec![ident("b")].into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn track_repeat_expand_auto_fill_returns_none() {
+        use super::generics::grid::GenericTrackRepeat;
+
+        let repeat = GenericTrackRepeat {
+            count: RepeatCount::AutoFill,
+            line_names: vec![vec![].into(), vec![].into()].into(),
+            track_sizes: vec![TrackSize::Breadth(TrackBreadth::Fr(1.))].into(),
+        };
+
+        assert!(repeat.expand_fixed().is_none());
+    }
+
+    #[test]
+    fn name_repeat_expand() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let repeat = NameRepeat {
+            count: RepeatCount::Number(2),
+            line_names: vec![vec![].into(), vec![ident("x")].into()].into(),
+        };
+
+        assert_eq!(
+            repeat.expand().unwrap(),
+            vec![
+                vec![].into(),
+                vec![ident("x")].into(),
+                vec![].into(),
+                vec![ident("x")].into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn name_repeat_expand_auto_fill_returns_none() {
+        let repeat = NameRepeat {
+            count: RepeatCount::AutoFill,
+            line_names: vec![vec![].into()].into(),
+        };
+
+        assert!(repeat.expand().is_none());
+    }
+
+    #[test]
+    fn line_name_list_resolved_names_at() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let line_name_list = LineNameList {
+            expanded_line_names_length: 2,
+            line_names: vec![
+                LineNameListValue::LineNames(vec![ident("a")].into()),
+                LineNameListValue::Repeat(NameRepeat {
+                    count: RepeatCount::AutoFill,
+                    line_names: vec![vec![ident("b")].into()].into(),
+                }),
+                LineNameListValue::LineNames(vec![ident("c")].into()),
+            ]
+            .into(),
+        };
+
+        assert_eq!(
+            line_name_list.resolved_names_at(0, 3),
+            Some(vec![ident("a")])
+        );
+        assert_eq!(
+            line_name_list.resolved_names_at(1, 3),
+            Some(vec![ident("b")])
+        );
+        assert_eq!(
+            line_name_list.resolved_names_at(2, 3),
+            Some(vec![ident("b")])
+        );
+        assert_eq!(
+            line_name_list.resolved_names_at(3, 3),
+            Some(vec![ident("b")])
+        );
+        assert_eq!(
+            line_name_list.resolved_names_at(4, 3),
+            Some(vec![ident("c")])
+        );
+        assert_eq!(line_name_list.resolved_names_at(5, 3), None);
+    }
+
+    #[test]
+    fn track_list_track_after_line() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let px = |v: f32| TrackSize::Breadth(TrackBreadth::Breadth(LengthPercentage::new_length(Length::new(v))));
+
+        let track_list = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![
+                TrackListValue::TrackSize(px(10.)),
+                TrackListValue::TrackSize(px(20.)),
+            ]
+            .into(),
+            line_names: vec![
+                vec![].into(),
+                vec![ident("header-end")].into(),
+                vec![].into(),
+            ]
+            .into(),
+        };
+
+        let track = track_list
+            .track_after_line(&ident("header-end"), 0, 0)
+            .unwrap();
+        assert_eq!(*track, px(20.));
+
+        assert!(track_list
+            .track_after_line(&ident("header-end"), 1, 0)
+            .is_none());
+        assert!(track_list.track_after_line(&ident("nope"), 0, 0).is_none());
+    }
+
+    #[test]
+    fn track_list_explicit_track_count() {
+        use super::generics::grid::GenericTrackRepeat;
+
+        let px = |v: f32| {
+            TrackSize::Breadth(TrackBreadth::Breadth(LengthPercentage::new_length(
+                Length::new(v),
+            )))
+        };
+
+        // Two plain sizes plus a repeat(3, ...) of two tracks each: 2 + 3*2 = 8.
+        let track_list = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![
+                TrackListValue::TrackSize(px(10.)),
+                TrackListValue::TrackRepeat(GenericTrackRepeat {
+                    count: RepeatCount::Number(3),
+                    line_names: vec![vec![].into(), vec![].into()].into(),
+                    track_sizes: vec![px(20.), px(30.)].into(),
+                }),
+                TrackListValue::TrackSize(px(40.)),
+            ]
+            .into(),
+            line_names: vec![vec![].into(), vec![].into(), vec![].into(), vec![].into()].into(),
+        };
+
+        assert_eq!(track_list.explicit_track_count(), 8);
+    }
+
+    #[test]
+    fn track_list_explicit_track_count_skips_auto_repeat() {
+        use super::generics::grid::GenericTrackRepeat;
+
+        let track_list = TrackList {
+            auto_repeat_index: 0,
+            values: vec![
+                TrackListValue::TrackRepeat(GenericTrackRepeat {
+                    count: RepeatCount::AutoFill,
+                    line_names: vec![vec![].into(), vec![].into()].into(),
+                    track_sizes: vec![TrackSize::Breadth(TrackBreadth::Fr(1.))].into(),
+                }),
+                TrackListValue::TrackSize(TrackSize::Breadth(TrackBreadth::Fr(1.))),
+            ]
+            .into(),
+            line_names: vec![vec![].into(), vec![].into(), vec![].into()].into(),
+        };
+
+        assert_eq!(track_list.explicit_track_count(), 1);
+    }
+
+    #[test]
+    fn track_list_diff_produces_single_insert() {
+        fn track_list(pxs: &[f32]) -> TrackList {
+            let values: Vec<_> = pxs
+                .iter()
+                .map(|&px| {
+                    TrackListValue::TrackSize(TrackSize::Breadth(TrackBreadth::Breadth(
+                        LengthPercentage::new_length(Length::new(px)),
+                    )))
+                })
+                .collect();
+            let line_names: Vec<crate::OwnedSlice<CustomIdent>> =
+                (0..=pxs.len()).map(|_| Default::default()).collect();
+            TrackList {
+                auto_repeat_index: usize::MAX,
+                values: values.into(),
+                line_names: line_names.into(),
+            }
+        }
+
+        let from = track_list(&[100., 200.]);
+        let to = track_list(&[100., 150., 200.]);
+
+        let edits = from.diff(&to);
+        assert_eq!(edits.len(), 1);
+        match &edits[0] {
+            TrackEdit::Insert { index, track, .. } => {
+                assert_eq!(*index, 1);
+                assert_eq!(
+                    *track,
+                    TrackSize::Breadth(TrackBreadth::Breadth(LengthPercentage::new_length(
+                        Length::new(150.)
+                    )))
+                );
+            },
+            other => panic!("expected an insert, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn track_list_to_css_never_trailing_space() {
+        use super::generics::grid::GenericTrackRepeat;
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let px = |v: f32| {
+            TrackSize::Breadth(TrackBreadth::Breadth(LengthPercentage::new_length(
+                Length::new(v),
+            )))
+        };
+
+        // Two plain tracks, no line names.
+        let no_names = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![TrackListValue::TrackSize(px(10.)), TrackListValue::TrackSize(px(20.))]
+                .into(),
+            line_names: vec![vec![].into(), vec![].into(), vec![].into()].into(),
+        };
+        assert_eq!(no_names.to_css_string(), "10px 20px");
+
+        // A single track.
+        let single = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![TrackListValue::TrackSize(px(10.))].into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        };
+        assert_eq!(single.to_css_string(), "10px");
+
+        // A trailing (empty) line-names list after the last track must not
+        // leave a stray space.
+        let trailing_empty_names = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![TrackListValue::TrackSize(px(10.))].into(),
+            line_names: vec![vec![ident("a")].into(), vec![].into()].into(),
+        };
+        assert_eq!(trailing_empty_names.to_css_string(), "[a] 10px");
+
+        // An auto-repeat as the last (and only) entry.
+        let auto_repeat_last = TrackList {
+            auto_repeat_index: 0,
+            values: vec![TrackListValue::TrackRepeat(GenericTrackRepeat {
+                count: RepeatCount::AutoFill,
+                line_names: vec![vec![].into(), vec![].into()].into(),
+                track_sizes: vec![px(10.)].into(),
+            })]
+            .into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        };
+        for css in [
+            no_names.to_css_string(),
+            single.to_css_string(),
+            trailing_empty_names.to_css_string(),
+            auto_repeat_last.to_css_string(),
+        ] {
+            assert!(!css.ends_with(' '), "unexpected trailing space in {:?}", css);
+        }
+    }
+}
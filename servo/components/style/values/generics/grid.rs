@@ -11,7 +11,7 @@ use crate::values::{CSSFloat, CustomIdent};
 use crate::{One, Zero};
 use cssparser::Parser;
 use std::fmt::{self, Write};
-use std::{cmp, usize};
+use std::usize;
 use style_traits::{CssWriter, ParseError, StyleParseErrorKind, ToCss};
 
 /// These are the limits that we choose to clamp grid line numbers to.
@@ -28,6 +28,7 @@ pub const MAX_GRID_LINE: i32 = 10000;
     Clone,
     Debug,
     Default,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -78,6 +79,25 @@ where
         self.ident.0 != atom!("") && self.line_num.is_zero() && !self.is_span
     }
 
+    /// Check whether this `<grid-line>` is a bare `<integer>` (no `span`,
+    /// no `<custom-ident>`). Distinct from `auto`, which is the same shape
+    /// but with a zero `line_num`.
+    pub fn is_line_number_only(&self) -> bool {
+        self.ident.0 == atom!("") && !self.line_num.is_zero() && !self.is_span
+    }
+
+    /// Check whether this `<grid-line>` is `span <integer>` with no
+    /// `<custom-ident>`.
+    pub fn is_span_only(&self) -> bool {
+        self.is_span && self.ident.0 == atom!("")
+    }
+
+    /// Check whether this `<grid-line>` is `span` with a `<custom-ident>`,
+    /// with or without an accompanying `<integer>`.
+    pub fn is_span_ident(&self) -> bool {
+        self.is_span && self.ident.0 != atom!("")
+    }
+
     /// Check if `self` makes `other` omittable according to the rules at:
     /// https://drafts.csswg.org/css-grid/#propdef-grid-column
     /// https://drafts.csswg.org/css-grid/#propdef-grid-area
@@ -141,10 +161,68 @@ where
     }
 }
 
-impl Parse for GridLine<specified::Integer> {
-    fn parse<'i, 't>(
+/// Serializes the `<grid-row-start> [/ <grid-column-start> [/ <grid-row-end>
+/// [/ <grid-column-end>]]]` form of the `grid-area` shorthand, composing
+/// `GridLine::can_omit` across the whole set of four lines rather than just
+/// a single pair.
+///
+/// The lines that are still omittable after dropping `grid-column-end`
+/// depend on whether `grid-column-end` itself was omittable in the first
+/// place, so this can't just call `can_omit` on each pair independently;
+/// e.g. `1 / 1 / 1 / 1` only collapses all the way down to `1` because each
+/// step's omission enables the next one to be checked.
+///
+/// <https://drafts.csswg.org/css-grid/#propdef-grid-area>
+pub fn serialize_grid_area<Integer, W>(
+    row_start: &GridLine<Integer>,
+    column_start: &GridLine<Integer>,
+    row_end: &GridLine<Integer>,
+    column_end: &GridLine<Integer>,
+    dest: &mut CssWriter<W>,
+) -> fmt::Result
+where
+    Integer: ToCss + PartialEq + Zero + One,
+    W: Write,
+{
+    row_start.to_css(dest)?;
+
+    let mut trailing_values = 3;
+    if column_start.can_omit(column_end) {
+        trailing_values -= 1;
+        if row_start.can_omit(row_end) {
+            trailing_values -= 1;
+            if row_start.can_omit(column_start) {
+                trailing_values -= 1;
+            }
+        }
+    }
+
+    for value in [column_start, row_end, column_end].iter().take(trailing_values) {
+        dest.write_str(" / ")?;
+        value.to_css(dest)?;
+    }
+    Ok(())
+}
+
+impl GridLine<specified::Integer> {
+    /// Like `parse`, but clamps an explicit `span 0` up to `span 1` instead
+    /// of rejecting it. Some non-CSS callers (e.g. layout code translating a
+    /// legacy attribute-derived line number) produce a `span 0` that the
+    /// strict CSS grammar correctly rejects but that's more useful clamped
+    /// than dropped entirely; `parse` itself is unaffected; and this is a
+    /// separate entry point, not a `ParsingMode` bit, since nothing else
+    /// about `<grid-line>` parsing needs to vary this way.
+    pub fn parse_lenient<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        Self::parse_impl(context, input, /* clamp_span_zero = */ true)
+    }
+
+    fn parse_impl<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
+        clamp_span_zero: bool,
     ) -> Result<Self, ParseError<'i>> {
         let mut grid_line = Self::auto();
         if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
@@ -155,57 +233,90 @@ impl Parse for GridLine<specified::Integer> {
         // This <grid-line> horror is simply,
         // [ span? && [ <custom-ident> || <integer> ] ]
         // And, for some magical reason, "span" should be the first or last value and not in-between.
+        //
+        // There are only three possible entities (`span`, `<integer>`, and
+        // `<custom-ident>`), each of which may appear at most once. We track
+        // their consumption with explicit flags instead of bounding the loop
+        // by an iteration count, so the acceptance rules stay self-documenting
+        // if the grammar ever grows another entity. The loop itself simply
+        // runs until none of the three entities matches at the current
+        // position.
         let mut val_before_span = false;
+        let mut consumed_span = false;
+        let mut consumed_integer = false;
+        let mut consumed_ident = false;
+        let mut consumed_zero_integer = false;
 
-        for _ in 0..3 {
-            // Maximum possible entities for <grid-line>
+        loop {
             let location = input.current_source_location();
             if input.try_parse(|i| i.expect_ident_matching("span")).is_ok() {
-                if grid_line.is_span {
+                if consumed_span {
                     return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
                 }
 
-                if !grid_line.line_num.is_zero() || grid_line.ident.0 != atom!("") {
+                if consumed_integer || consumed_ident {
                     val_before_span = true;
                 }
 
                 grid_line.is_span = true;
+                consumed_span = true;
             } else if let Ok(i) = input.try_parse(|i| specified::Integer::parse(context, i)) {
-                // FIXME(emilio): Probably shouldn't reject if it's calc()...
                 let value = i.value();
-                if value == 0 || val_before_span || !grid_line.line_num.is_zero() {
+                if (value == 0 && !clamp_span_zero) || val_before_span || consumed_integer {
                     return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
                 }
+                consumed_zero_integer = value == 0;
 
-                grid_line.line_num = specified::Integer::new(cmp::max(
-                    MIN_GRID_LINE,
-                    cmp::min(value, MAX_GRID_LINE),
-                ));
+                // Clamp, but keep track of whether this came from a `calc()`
+                // expression so it still round-trips through `to_css` as
+                // `calc(...)` rather than losing that context.
+                grid_line.line_num = i.clamp(MIN_GRID_LINE, MAX_GRID_LINE);
+                consumed_integer = true;
             } else if let Ok(name) = input.try_parse(|i| CustomIdent::parse(i, &["auto"])) {
-                if val_before_span || grid_line.ident.0 != atom!("") {
+                if val_before_span || consumed_ident {
                     return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
                 }
                 // NOTE(emilio): `span` is consumed above, so we only need to
                 // reject `auto`.
                 grid_line.ident = name;
+                consumed_ident = true;
             } else {
                 break;
             }
         }
 
+        // Use the position right after the last consumed entity for these
+        // errors too, matching the `location.new_custom_error` calls above,
+        // so devtools reports a consistent error position regardless of
+        // which check in this function rejected the input.
+        let location = input.current_source_location();
+
+        if consumed_zero_integer && !grid_line.is_span {
+            // `clamp_span_zero` only rehabilitates a zero *span* count; a
+            // bare `0` (no `span`) is invalid either way, and this would
+            // otherwise fall through to `is_auto()` below and be silently
+            // accepted as `auto`.
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+
         if grid_line.is_auto() {
-            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
         }
 
         if grid_line.is_span {
+            if consumed_zero_integer {
+                debug_assert!(clamp_span_zero);
+                grid_line.line_num = grid_line.line_num.clamp(1, MAX_GRID_LINE);
+            }
+
             if !grid_line.line_num.is_zero() {
                 if grid_line.line_num.value() <= 0 {
                     // disallow negative integers for grid spans
-                    return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+                    return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
                 }
             } else if grid_line.ident.0 == atom!("") {
                 // integer could be omitted
-                return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+                return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
             }
         }
 
@@ -213,6 +324,15 @@ impl Parse for GridLine<specified::Integer> {
     }
 }
 
+impl Parse for GridLine<specified::Integer> {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        Self::parse_impl(context, input, /* clamp_span_zero = */ false)
+    }
+}
+
 /// A track breadth for explicit grid track sizing. It's generic solely to
 /// avoid re-implementing it for the computed type.
 ///
@@ -246,6 +366,20 @@ pub enum GenericTrackBreadth<L> {
 
 pub use self::GenericTrackBreadth as TrackBreadth;
 
+// Can't derive `Hash` because of the `Fr(CSSFloat)` variant: `f32` isn't
+// `Hash`. Hash its bits instead, like `f32::total_cmp`-adjacent code
+// elsewhere does when it needs a totally-ordered/hashable view of a float.
+impl<L: std::hash::Hash> std::hash::Hash for GenericTrackBreadth<L> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            TrackBreadth::Breadth(ref length) => length.hash(state),
+            TrackBreadth::Fr(fr) => fr.to_bits().hash(state),
+            TrackBreadth::Auto | TrackBreadth::MinContent | TrackBreadth::MaxContent => {},
+        }
+    }
+}
+
 impl<L> TrackBreadth<L> {
     /// Check whether this is a `<fixed-breadth>` (i.e., it only has `<length-percentage>`)
     ///
@@ -254,6 +388,36 @@ impl<L> TrackBreadth<L> {
     pub fn is_fixed(&self) -> bool {
         matches!(*self, TrackBreadth::Breadth(..))
     }
+
+    /// Returns the flex factor if this is a `<flex>` value, or `None` otherwise.
+    #[inline]
+    pub fn as_fr(&self) -> Option<CSSFloat> {
+        match *self {
+            TrackBreadth::Fr(fr) => Some(fr),
+            _ => None,
+        }
+    }
+
+    /// Check whether this is a `<flex>` value.
+    #[inline]
+    pub fn is_fr(&self) -> bool {
+        self.as_fr().is_some()
+    }
+
+    /// Maps the `<length-percentage>` payload of a `Breadth` through `f`,
+    /// leaving the other (unit-less) variants unchanged. This is the usual
+    /// way to convert a `TrackBreadth<L>` to a `TrackBreadth<U>`, e.g. when
+    /// going from specified to computed values outside of the normal
+    /// `ToComputedValue` machinery.
+    pub fn map<U>(&self, f: impl FnOnce(&L) -> U) -> TrackBreadth<U> {
+        match *self {
+            TrackBreadth::Breadth(ref length) => TrackBreadth::Breadth(f(length)),
+            TrackBreadth::Fr(fr) => TrackBreadth::Fr(fr),
+            TrackBreadth::Auto => TrackBreadth::Auto,
+            TrackBreadth::MinContent => TrackBreadth::MinContent,
+            TrackBreadth::MaxContent => TrackBreadth::MaxContent,
+        }
+    }
 }
 
 /// A `<track-size>` type for explicit grid track sizing. Like `<track-breadth>`, this is
@@ -263,6 +427,7 @@ impl<L> TrackBreadth<L> {
 #[derive(
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -306,6 +471,44 @@ impl<L> TrackSize<L> {
         matches!(*self, TrackSize::Breadth(TrackBreadth::Auto)) // FIXME: can't use Self::INITIAL_VALUE here yet: https://github.com/rust-lang/rust/issues/66585
     }
 
+    /// The `auto`-like minimum breadth of a `fit-content()` track. Kept as
+    /// an associated const purely so `min_breadth` can hand out a `&`
+    /// reference for a variant that doesn't otherwise store one.
+    const FIT_CONTENT_MIN: TrackBreadth<L> = TrackBreadth::Auto;
+
+    /// Returns this track size's minimum `<track-breadth>`, per the CSS
+    /// Grid sizing algorithm's track-size normalization
+    /// (<https://drafts.csswg.org/css-grid/#algo-terms>): for `Breadth(b)`
+    /// it's `b`, for `Minmax(min, _)` it's `min`, and for `FitContent(_)`
+    /// it's `auto`, since `fit-content(a)` sizes as `minmax(auto, a)`
+    /// clamped by `max-content`.
+    pub fn min_breadth(&self) -> &TrackBreadth<L> {
+        match *self {
+            TrackSize::Breadth(ref breadth) => breadth,
+            TrackSize::Minmax(ref min, _) => min,
+            TrackSize::FitContent(_) => &Self::FIT_CONTENT_MIN,
+        }
+    }
+
+    /// Returns this track size's maximum `<track-breadth>`: for `Breadth(b)`
+    /// it's `b`, for `Minmax(_, max)` it's `max`, and for `FitContent(lp)`
+    /// it's `lp` itself, since that's the clamp `fit-content()` applies.
+    pub fn max_breadth(&self) -> &TrackBreadth<L> {
+        match *self {
+            TrackSize::Breadth(ref breadth) => breadth,
+            TrackSize::Minmax(_, ref max) => max,
+            TrackSize::FitContent(ref lp) => {
+                // `fit-content()`'s argument is a `<length-percentage>` per
+                // the grammar; the parser (`TrackSize::parse`) only ever
+                // constructs `Breadth(..)` here, so this stays a fixed
+                // breadth even though the type itself is generic enough to
+                // hold any `<track-breadth>`.
+                debug_assert!(lp.is_fixed(), "fit-content() argument must be a <length-percentage>");
+                lp
+            },
+        }
+    }
+
     /// Check whether this is a `<fixed-size>`
     ///
     /// <https://drafts.csswg.org/css-grid/#typedef-fixed-size>
@@ -329,6 +532,64 @@ impl<L> TrackSize<L> {
             TrackSize::FitContent(_) => false,
         }
     }
+
+    /// Check whether this is a `<flex>` (i.e. `fr`) value, whether standalone
+    /// or as one side of a `minmax()`.
+    pub fn is_flexible(&self) -> bool {
+        match *self {
+            TrackSize::Breadth(ref breadth) => breadth.is_fr(),
+            TrackSize::Minmax(ref min, ref max) => min.is_fr() || max.is_fr(),
+            TrackSize::FitContent(_) => false,
+        }
+    }
+
+    /// Check whether this is content-sized, i.e. it involves `min-content`,
+    /// `max-content`, `auto`, or `fit-content()`, and has no fixed length
+    /// component.
+    pub fn is_content_sized(&self) -> bool {
+        fn is_intrinsic_keyword<L>(breadth: &TrackBreadth<L>) -> bool {
+            matches!(
+                *breadth,
+                TrackBreadth::Auto | TrackBreadth::MinContent | TrackBreadth::MaxContent
+            )
+        }
+
+        if self.is_fixed() {
+            return false;
+        }
+
+        match *self {
+            TrackSize::Breadth(ref breadth) => is_intrinsic_keyword(breadth),
+            TrackSize::Minmax(ref min, ref max) => {
+                is_intrinsic_keyword(min) || is_intrinsic_keyword(max)
+            },
+            TrackSize::FitContent(_) => true,
+        }
+    }
+
+    /// Maps the `<length-percentage>` payload(s) of this track size through
+    /// `f`, recursing into `Minmax`'s two breadths and `FitContent`'s one,
+    /// mirroring `TrackBreadth::map`.
+    pub fn map<U>(&self, f: impl Fn(&L) -> U + Copy) -> TrackSize<U> {
+        match *self {
+            TrackSize::Breadth(ref breadth) => TrackSize::Breadth(breadth.map(f)),
+            TrackSize::Minmax(ref min, ref max) => TrackSize::Minmax(min.map(f), max.map(f)),
+            TrackSize::FitContent(ref breadth) => TrackSize::FitContent(breadth.map(f)),
+        }
+    }
+
+    /// `minmax(<inflexible-breadth>, <track-breadth>)` disallows a flex
+    /// `<fr>` in the first (minimum) position; only the second (maximum)
+    /// position may be flexible. The parser (`TrackSize::parse`) already
+    /// enforces this by construction, since its `inflexible_breadth`
+    /// production has no `<flex>` arm at all — this is just a debug-only
+    /// check on that invariant, for code that builds a `TrackSize` some
+    /// other way (e.g. `map`, or a struct literal in a test).
+    pub fn assert_invariants(&self) {
+        if let TrackSize::Minmax(ref min, _) = *self {
+            debug_assert!(!min.is_fr(), "minmax() may not have a flex minimum");
+        }
+    }
 }
 
 impl<L> Default for TrackSize<L> {
@@ -391,6 +652,23 @@ pub struct GenericImplicitGridTracks<T>(
 pub use self::GenericImplicitGridTracks as ImplicitGridTracks;
 
 impl<T: fmt::Debug + Default + PartialEq> ImplicitGridTracks<T> {
+    /// Creates an `ImplicitGridTracks` from a vector of tracks.
+    ///
+    /// A single `Default` track should never be stored this way; that's
+    /// represented by the empty (i.e. `auto`) list instead.
+    pub fn from_tracks(tracks: Vec<T>) -> Self {
+        debug_assert!(
+            tracks.len() != 1 || tracks[0] != Default::default(),
+            "a single default track should be represented as the empty list"
+        );
+        ImplicitGridTracks(tracks.into())
+    }
+
+    /// Returns the number of tracks.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
     /// Returns true if current value is same as its initial value (i.e. auto).
     pub fn is_initial(&self) -> bool {
         debug_assert_ne!(
@@ -399,6 +677,24 @@ impl<T: fmt::Debug + Default + PartialEq> ImplicitGridTracks<T> {
         );
         self.0.is_empty()
     }
+
+    /// Returns the implicit track that `implicit_index` maps to, cycling
+    /// through the list per the "repeat the last implicit track list"
+    /// rule (<https://drafts.csswg.org/css-grid/#auto-tracks>).
+    ///
+    /// `auto` (the empty list) behaves as if it were a single `T::default()`
+    /// track, so every index maps to that. This returns an owned `T` rather
+    /// than `&T` since the `auto` case has no track in `self.0` to borrow
+    /// from.
+    pub fn track_for(&self, implicit_index: usize) -> T
+    where
+        T: Clone,
+    {
+        if self.0.is_empty() {
+            return T::default();
+        }
+        self.0[implicit_index % self.0.len()].clone()
+    }
 }
 
 /// Helper function for serializing identifiers with a prefix and suffix, used
@@ -427,6 +723,34 @@ where
     Ok(())
 }
 
+/// A `RepeatCount`'s `Integer` payload that can report itself as a plain,
+/// non-negative repetition count.
+///
+/// The various `TrackRepeat`/`TrackList`/`GridTemplateComponent` expansion
+/// methods below (`expand`, `resolve_tracks`, `enumerate_lines`,
+/// `to_devtools`, `track_after_line`, `explicit_track_count`, `diff`) are
+/// otherwise pure data-shape transforms generic over `L`/`I`, but they all
+/// need to turn a `RepeatCount::Number(n)` into a `usize` to know how many
+/// times to repeat. That's only meaningful once `n` is a resolved, concrete
+/// integer (as `computed::Integer` is): a `specified::Integer` may still
+/// carry an un-resolved `calc()`, so this trait — rather than a bare `as
+/// usize` cast — is what keeps these methods from silently compiling for a
+/// specified `TrackRepeat<L, I>` they were never meant to support.
+pub trait ResolvedRepeatCount {
+    /// Returns this value as a repetition count, clamping negative values to
+    /// zero (parsing already rejects non-positive `<integer>`s here, but
+    /// this keeps the conversion total instead of panicking on a
+    /// programmatically-constructed value).
+    fn resolved_count(&self) -> usize;
+}
+
+impl ResolvedRepeatCount for i32 {
+    #[inline]
+    fn resolved_count(&self) -> usize {
+        (*self).max(0) as usize
+    }
+}
+
 /// The initial argument of the `repeat` function.
 ///
 /// <https://drafts.csswg.org/css-grid/#typedef-track-repeat>
@@ -434,6 +758,7 @@ where
     Clone,
     Copy,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -452,7 +777,44 @@ pub enum RepeatCount<Integer> {
     AutoFit,
 }
 
+impl<Integer> RepeatCount<Integer> {
+    /// Whether this is an automatic repeat count, i.e. `auto-fill` or `auto-fit`.
+    #[inline]
+    pub fn is_auto(&self) -> bool {
+        matches!(*self, RepeatCount::AutoFill | RepeatCount::AutoFit)
+    }
+
+    /// Returns the concrete repeat count, if this isn't `auto-fill`/`auto-fit`.
+    #[inline]
+    pub fn as_number(&self) -> Option<&Integer> {
+        match *self {
+            RepeatCount::Number(ref n) => Some(n),
+            RepeatCount::AutoFill | RepeatCount::AutoFit => None,
+        }
+    }
+}
+
+impl<Integer: ResolvedRepeatCount> RepeatCount<Integer> {
+    /// Resolves this count to a plain repetition count: `auto_repeat_count`
+    /// for `AutoFill`/`AutoFit` (the actual count is only known once layout
+    /// resolves how many auto-repeated tracks fit), or the concrete `Number`
+    /// otherwise.
+    #[inline]
+    pub fn resolved(&self, auto_repeat_count: usize) -> usize {
+        match *self {
+            RepeatCount::Number(ref n) => n.resolved_count(),
+            RepeatCount::AutoFill | RepeatCount::AutoFit => auto_repeat_count,
+        }
+    }
+}
+
 impl Parse for RepeatCount<specified::Integer> {
+    // A `RepeatCount::Number` is always in `[1, MAX_GRID_LINE]`: the lower
+    // bound is enforced by `parse_positive` rejecting zero and negative
+    // values outright (a hard parse error, not a clamp), while the upper
+    // bound is silently clamped down to `MAX_GRID_LINE`, matching how
+    // overlarge `<grid-line>` values are clamped rather than rejected.
+    // https://drafts.csswg.org/css-grid/#typedef-track-repeat
     fn parse<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
@@ -474,6 +836,7 @@ impl Parse for RepeatCount<specified::Integer> {
 #[derive(
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -498,11 +861,44 @@ pub struct GenericTrackRepeat<L, I> {
 
 pub use self::GenericTrackRepeat as TrackRepeat;
 
+impl<L, I> TrackRepeat<L, I> {
+    /// Returns the number of `<line-names>` produced by a single repetition,
+    /// i.e. `self.line_names.len()`. Combined with a repetition count
+    /// (static for `RepeatCount::Number`, or layout-provided for
+    /// `auto-fill`/`auto-fit`), this lets a caller compute the total number
+    /// of line names this `<track-repeat>` expands to without walking
+    /// `line_names` itself.
+    pub fn line_names_per_repetition(&self) -> usize {
+        self.line_names.len()
+    }
+
+    /// Returns the number of `<track-size>` values produced by a single
+    /// repetition, i.e. `self.track_sizes.len()`.
+    pub fn track_sizes_per_repetition(&self) -> usize {
+        self.track_sizes.len()
+    }
+
+    /// Debug-only check of the invariant documented on the `line_names`
+    /// field: for N `<track-size>` values, there must be exactly N+1
+    /// `<line-names>`. A violation here would corrupt `to_css`, which zips
+    /// `track_sizes` with `line_names` assuming that shape.
+    #[inline]
+    pub fn assert_invariants(&self) {
+        debug_assert_eq!(
+            self.line_names.len(),
+            self.track_sizes.len() + 1,
+            "TrackRepeat must have one more <line-names> group than <track-size> values"
+        );
+    }
+}
+
 impl<L: ToCss, I: ToCss> ToCss for TrackRepeat<L, I> {
     fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
     where
         W: Write,
     {
+        self.assert_invariants();
+
         dest.write_str("repeat(")?;
         self.count.to_css(dest)?;
         dest.write_str(", ")?;
@@ -532,11 +928,46 @@ impl<L: ToCss, I: ToCss> ToCss for TrackRepeat<L, I> {
     }
 }
 
+impl<L: Clone, I: ResolvedRepeatCount> TrackRepeat<L, I> {
+    /// Expands this `<track-repeat>` into `(preceding line names, track
+    /// size)` pairs, repeating its `track_sizes`/`line_names` the
+    /// appropriate number of times.
+    ///
+    /// For a fixed `<integer>` count the repetition count comes from
+    /// `self.count`; for `<auto-repeat>` (`auto-fill`/`auto-fit`) it's
+    /// `auto_repeat_count`, supplied by the caller (grid layout, which
+    /// alone knows how many auto-repeated tracks fit).
+    ///
+    /// Between two repetitions, the trailing `<line-names>` group (see the
+    /// field docs on `line_names`) is merged in front of the next
+    /// repetition's leading names, since both name the same line. The
+    /// trailing group that follows the very last repeated track is dropped,
+    /// since there is no following track to pair it with; callers that need
+    /// it can still read `self.line_names.last()` themselves.
+    pub fn expand(&self, auto_repeat_count: usize) -> Vec<(Vec<CustomIdent>, TrackSize<L>)> {
+        let repeat_count = self.count.resolved(auto_repeat_count);
+        let trailing = self.line_names.get(self.track_sizes.len());
+
+        let mut result = Vec::with_capacity(repeat_count * self.track_sizes.len());
+        for i in 0..repeat_count {
+            for (j, (size, names)) in self.track_sizes.iter().zip(self.line_names.iter()).enumerate() {
+                let mut names = names.to_vec();
+                if let (true, Some(trailing)) = (i > 0 && j == 0, trailing) {
+                    names = trailing.iter().cloned().chain(names).collect();
+                }
+                result.push((names, size.clone()));
+            }
+        }
+        result
+    }
+}
+
 /// Track list values. Can be <track-size> or <track-repeat>
 #[derive(
     Animate,
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -559,7 +990,8 @@ impl<L, I> TrackListValue<L, I> {
     // FIXME: can't use TrackSize::initial_value() here b/c rustc error "is not yet stable as a const fn"
     const INITIAL_VALUE: Self = TrackListValue::TrackSize(TrackSize::Breadth(TrackBreadth::Auto));
 
-    fn is_repeat(&self) -> bool {
+    /// Returns true if `self` is a `<track-repeat>`.
+    pub fn is_repeat(&self) -> bool {
         matches!(*self, TrackListValue::TrackRepeat(..))
     }
 
@@ -570,6 +1002,23 @@ impl<L, I> TrackListValue<L, I> {
             TrackListValue::TrackSize(TrackSize::Breadth(TrackBreadth::Auto))
         ) // FIXME: can't use Self::INITIAL_VALUE here yet: https://github.com/rust-lang/rust/issues/66585
     }
+
+    /// Returns the `<track-size>` this value holds, if it isn't a
+    /// `<track-repeat>`.
+    pub fn as_track_size(&self) -> Option<&TrackSize<L>> {
+        match *self {
+            TrackListValue::TrackSize(ref size) => Some(size),
+            TrackListValue::TrackRepeat(..) => None,
+        }
+    }
+
+    /// Returns the `<track-repeat>` this value holds, if it is one.
+    pub fn as_repeat(&self) -> Option<&TrackRepeat<L, I>> {
+        match *self {
+            TrackListValue::TrackRepeat(ref repeat) => Some(repeat),
+            TrackListValue::TrackSize(..) => None,
+        }
+    }
 }
 
 impl<L, I> Default for TrackListValue<L, I> {
@@ -585,6 +1034,7 @@ impl<L, I> Default for TrackListValue<L, I> {
 #[derive(
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -616,10 +1066,60 @@ impl<L, I> TrackList<L, I> {
         !self.values.iter().any(|v| v.is_repeat())
     }
 
+    /// Whether this track list has no tracks at all, i.e. is equivalent to
+    /// `auto`. This can happen for a `GridTemplateComponent::TrackList` built
+    /// programmatically (e.g. `from_track_list`) rather than parsed, since
+    /// the parser itself never produces an empty `<track-list>`.
+    pub fn is_auto(&self) -> bool {
+        self.values.is_empty()
+    }
+
     /// Whether this track list has an `<auto-repeat>` value.
+    ///
+    /// A track list may have at most one `<auto-repeat>`; parsing
+    /// (`TrackList::parse`) already rejects a second one, so this is just a
+    /// debug-only sanity check on that invariant.
     pub fn has_auto_repeat(&self) -> bool {
+        debug_assert!(
+            self.values
+                .iter()
+                .filter(
+                    |v| matches!(v, TrackListValue::TrackRepeat(ref r) if r.count.is_auto())
+                )
+                .count()
+                <= 1,
+            "a TrackList must have at most one <auto-repeat>"
+        );
         self.auto_repeat_index < self.values.len()
     }
+
+    /// Returns the number of `<line-names>` positions in this track list,
+    /// i.e. `self.line_names.len()`. This is always one more than
+    /// `self.values.len()`.
+    pub fn line_name_count(&self) -> usize {
+        self.line_names.len()
+    }
+
+    /// Returns whether this track list's line names are reconcilable with a
+    /// subgrid inheriting into a parent with `parent_tracks` tracks. A
+    /// subgrid can have at most one `<line-names>` group per grid line of
+    /// its parent, and a track list with N tracks has N+1 grid lines.
+    pub fn subgrid_fits(&self, parent_tracks: usize) -> bool {
+        self.line_name_count() <= parent_tracks + 1
+    }
+
+    /// Iterates over each leading `<line-names>` list paired with the track
+    /// value that follows it, respecting the "N values, N+1 name lists"
+    /// invariant. The final tuple, for the trailing `<line-names>` list, has
+    /// `None` in place of a track value.
+    pub fn iter_tracks_with_line_names(
+        &self,
+    ) -> impl Iterator<Item = (&crate::OwnedSlice<CustomIdent>, Option<&TrackListValue<L, I>>)>
+    {
+        self.line_names
+            .iter()
+            .zip(self.values.iter().map(Some).chain(std::iter::once(None)))
+    }
 }
 
 impl<L: ToCss, I: ToCss> ToCss for TrackList<L, I> {
@@ -627,28 +1127,33 @@ impl<L: ToCss, I: ToCss> ToCss for TrackList<L, I> {
     where
         W: Write,
     {
-        let mut values_iter = self.values.iter().peekable();
-        let mut line_names_iter = self.line_names.iter().peekable();
+        // The parser never produces an empty `<track-list>` (the grammar
+        // requires at least one track), but `GridTemplateComponent` can be
+        // built with one programmatically (e.g. `from_track_list`). Rather
+        // than serializing that as nothing at all, treat it the same as
+        // `GridTemplateComponent::None`, which is what it's equivalent to.
+        if self.is_auto() {
+            return dest.write_str("none");
+        }
 
-        for idx in 0.. {
-            let names = line_names_iter.next().unwrap(); // This should exist!
-            concat_serialize_idents("[", "]", names, " ", dest)?;
+        let mut iter = self.iter_tracks_with_line_names().peekable();
 
-            match values_iter.next() {
-                Some(value) => {
-                    if !names.is_empty() {
-                        dest.write_char(' ')?;
-                    }
+        while let Some((names, value)) = iter.next() {
+            concat_serialize_idents("[", "]", names, " ", dest)?;
 
-                    value.to_css(dest)?;
-                },
+            let value = match value {
+                Some(value) => value,
                 None => break,
+            };
+
+            if !names.is_empty() {
+                dest.write_char(' ')?;
             }
+            value.to_css(dest)?;
 
-            if values_iter.peek().is_some() ||
-                line_names_iter.peek().map_or(false, |v| !v.is_empty()) ||
-                (idx + 1 == self.auto_repeat_index)
-            {
+            if iter.peek().map_or(false, |&(next_names, next_value)| {
+                next_value.is_some() || !next_names.is_empty()
+            }) {
                 dest.write_char(' ')?;
             }
         }
@@ -657,6 +1162,294 @@ impl<L: ToCss, I: ToCss> ToCss for TrackList<L, I> {
     }
 }
 
+impl<L: Clone, I: ResolvedRepeatCount> TrackList<L, I> {
+    /// Yields `(line_number, names)` for every named `<line-names>` position
+    /// in this track list, with `<auto-repeat>` expanded to
+    /// `auto_repeat_count` tracks. Line numbers start at 0 for the line
+    /// before the first track and increase by one per (expanded) track, so
+    /// they line up with the indices grid layout assigns to lines.
+    ///
+    /// This mirrors the expansion `GridTemplateComponent::to_devtools`
+    /// performs, but keeps structured `CustomIdent`s tagged with their line
+    /// number instead of serializing them.
+    pub fn enumerate_lines(&self, auto_repeat_count: usize) -> Vec<(usize, Vec<CustomIdent>)> {
+        let mut result = Vec::new();
+        let mut line_number = 0usize;
+        let mut line_names_iter = self.line_names.iter();
+
+        for value in self.values.iter() {
+            if let Some(names) = line_names_iter.next() {
+                if !names.is_empty() {
+                    result.push((line_number, names.to_vec()));
+                }
+            }
+
+            match *value {
+                TrackListValue::TrackSize(..) => {
+                    line_number += 1;
+                },
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    let repeat_count = repeat.count.resolved(auto_repeat_count);
+                    for _ in 0..repeat_count {
+                        for names in repeat.line_names.iter().take(repeat.track_sizes.len()) {
+                            if !names.is_empty() {
+                                result.push((line_number, names.to_vec()));
+                            }
+                            line_number += 1;
+                        }
+                        if let Some(last_names) = repeat.line_names.last() {
+                            if !last_names.is_empty() {
+                                result.push((line_number, last_names.to_vec()));
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        if let Some(names) = line_names_iter.next() {
+            if !names.is_empty() {
+                result.push((line_number, names.to_vec()));
+            }
+        }
+
+        result
+    }
+
+    /// Returns the track immediately following the `occurrence`-th
+    /// (0-indexed) appearance of the named line `name`, after expanding any
+    /// fixed-count `repeat()`. If this list has an `<auto-repeat>` block,
+    /// its line names and tracks are counted `auto_repeat_count` times.
+    ///
+    /// Returns `None` if `name` doesn't occur `occurrence + 1` times, or if
+    /// its last occurrence isn't followed by a track (e.g. it's the final
+    /// line of the list).
+    ///
+    /// Only the `<line-names>` written directly before a track are searched:
+    /// the list's own names before a plain track, and a repeat's own names
+    /// before each of its tracks. Names written before a `repeat()`, and a
+    /// repeat's trailing names, are not searched.
+    pub fn track_after_line(
+        &self,
+        name: &CustomIdent,
+        occurrence: usize,
+        auto_repeat_count: usize,
+    ) -> Option<&TrackSize<L>> {
+        let mut seen = 0;
+        let mut line_names_iter = self.line_names.iter();
+
+        for (index, value) in self.values.iter().enumerate() {
+            let names = line_names_iter.next();
+
+            match *value {
+                TrackListValue::TrackSize(ref size) => {
+                    if names.map_or(false, |n| n.iter().any(|n| n == name)) {
+                        if seen == occurrence {
+                            return Some(size);
+                        }
+                        seen += 1;
+                    }
+                },
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    let count = if index == self.auto_repeat_index {
+                        auto_repeat_count
+                    } else {
+                        repeat.count.resolved(0)
+                    };
+                    for _ in 0..count {
+                        for (j, size) in repeat.track_sizes.iter().enumerate() {
+                            if repeat
+                                .line_names
+                                .get(j)
+                                .map_or(false, |n| n.iter().any(|n| n == name))
+                            {
+                                if seen == occurrence {
+                                    return Some(size);
+                                }
+                                seen += 1;
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        None
+    }
+
+    /// Returns an upper bound on the number of explicit tracks in this list,
+    /// as if every `<track-repeat>` with a concrete `Number` count were
+    /// expanded, without actually allocating the expansion. The
+    /// `<auto-repeat>` entry, if any, is skipped since its count isn't known
+    /// until layout.
+    ///
+    /// Clamped to `MAX_GRID_LINE` to match the grid spec's overlarge-grid
+    /// rules.
+    pub fn explicit_track_count(&self) -> usize {
+        let mut count = 0usize;
+        for (index, value) in self.values.iter().enumerate() {
+            if index == self.auto_repeat_index {
+                continue;
+            }
+            count += match *value {
+                TrackListValue::TrackSize(..) => 1,
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    repeat.count.resolved(0) * repeat.track_sizes.len()
+                },
+            };
+        }
+        count.min(MAX_GRID_LINE as usize)
+    }
+
+    /// Expands any statically-sized `repeat()` into individual tracks,
+    /// pairing each with the serialized `<line-names>` that precede it.
+    /// `<auto-repeat>` cannot be expanded without knowing the resolved
+    /// track count from layout, so it contributes no tracks here.
+    ///
+    /// A plain track gets the list's own `<line-names>` before it; a
+    /// repeated track gets the repeat's own names for its position on the
+    /// first repetition and no names on later ones. Names written before a
+    /// `repeat()`, and a repeat's trailing names, are not included.
+    fn expanded_tracks(&self) -> Vec<(Vec<String>, TrackSize<L>)>
+    where
+        L: ToCss,
+    {
+        fn names_to_strings(names: &[CustomIdent]) -> Vec<String> {
+            names.iter().map(|n| n.to_css_string()).collect()
+        }
+
+        let mut result = vec![];
+        let mut line_names_iter = self.line_names.iter();
+        for (index, value) in self.values.iter().enumerate() {
+            let names = line_names_iter.next().map_or(vec![], |n| names_to_strings(n));
+
+            if index == self.auto_repeat_index {
+                continue;
+            }
+
+            match *value {
+                TrackListValue::TrackSize(ref size) => result.push((names, size.clone())),
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    let count = repeat.count.resolved(0);
+                    for i in 0..count {
+                        for (j, size) in repeat.track_sizes.iter().enumerate() {
+                            let names = if i == 0 {
+                                repeat
+                                    .line_names
+                                    .get(j)
+                                    .map_or(vec![], |n| names_to_strings(n))
+                            } else {
+                                vec![]
+                            };
+                            result.push((names, size.clone()));
+                        }
+                    }
+                },
+            }
+        }
+        result
+    }
+
+    /// Produces the minimal sequence of edits (insert / remove / change)
+    /// needed to turn `self`'s expanded tracks into `other`'s, computed with
+    /// a Wagner-Fischer edit-distance table. Useful for animating or
+    /// visualizing a structural transition between two track lists.
+    pub fn diff(&self, other: &Self) -> Vec<TrackEdit<L>>
+    where
+        L: ToCss + PartialEq,
+    {
+        let from = self.expanded_tracks();
+        let to = other.expanded_tracks();
+
+        let n = from.len();
+        let m = to.len();
+        let mut dp = vec![vec![0usize; m + 1]; n + 1];
+        for (i, row) in dp.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for j in 0..=m {
+            dp[0][j] = j;
+        }
+        for i in 1..=n {
+            for j in 1..=m {
+                dp[i][j] = if from[i - 1] == to[j - 1] {
+                    dp[i - 1][j - 1]
+                } else {
+                    1 + dp[i - 1][j].min(dp[i][j - 1]).min(dp[i - 1][j - 1])
+                };
+            }
+        }
+
+        let mut edits = vec![];
+        let (mut i, mut j) = (n, m);
+        while i > 0 || j > 0 {
+            if i > 0 && j > 0 && from[i - 1] == to[j - 1] {
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+
+            if i > 0 && j > 0 && dp[i][j] == dp[i - 1][j - 1] + 1 {
+                let (line_names, track) = to[j - 1].clone();
+                edits.push(TrackEdit::Change {
+                    index: j - 1,
+                    track,
+                    line_names,
+                });
+                i -= 1;
+                j -= 1;
+            } else if j > 0 && dp[i][j] == dp[i][j - 1] + 1 {
+                let (line_names, track) = to[j - 1].clone();
+                edits.push(TrackEdit::Insert {
+                    index: j - 1,
+                    track,
+                    line_names,
+                });
+                j -= 1;
+            } else {
+                i -= 1;
+                edits.push(TrackEdit::Remove { index: i });
+            }
+        }
+
+        edits.reverse();
+        edits
+    }
+}
+
+/// A single edit needed to turn one expanded `<track-list>` into another, as
+/// produced by `TrackList::diff`. `Insert` and `Change` indices refer to the
+/// position within the resulting (target) expanded track list; `Remove`'s
+/// index refers to the position within the source list.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenericTrackEdit<L> {
+    /// Insert `track` (with its leading line names) at `index`.
+    Insert {
+        /// The position of the new track in the resulting list.
+        index: usize,
+        /// The size of the new track.
+        track: TrackSize<L>,
+        /// The serialized `<line-names>` immediately preceding the track.
+        line_names: Vec<String>,
+    },
+    /// Remove the track at `index`.
+    Remove {
+        /// The position of the removed track in the source list.
+        index: usize,
+    },
+    /// Replace the track at `index` with a new size and/or line names.
+    Change {
+        /// The position of the changed track in the resulting list.
+        index: usize,
+        /// The new size of the track.
+        track: TrackSize<L>,
+        /// The new serialized `<line-names>` immediately preceding the track.
+        line_names: Vec<String>,
+    },
+}
+
+pub use self::GenericTrackEdit as TrackEdit;
+
 /// The `<name-repeat>` for subgrids.
 ///
 /// <name-repeat> = repeat( [ <integer [1,∞]> | auto-fill ], <line-names>+)
@@ -665,6 +1458,7 @@ impl<L: ToCss, I: ToCss> ToCss for TrackList<L, I> {
 #[derive(
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -712,12 +1506,22 @@ impl<I> NameRepeat<I> {
     pub fn is_auto_fill(&self) -> bool {
         matches!(self.count, RepeatCount::AutoFill)
     }
+
+    /// Returns the repetition count for the `RepeatCount::Number` case, or
+    /// `None` for `auto-fill`. `NameRepeat`'s `count` is never `AutoFit`
+    /// (parsing rejects it; see the field doc), so this only needs to
+    /// distinguish those two cases.
+    #[inline]
+    pub fn number(&self) -> Option<&I> {
+        self.count.as_number()
+    }
 }
 
 /// A single value for `<line-names>` or `<name-repeat>`.
 #[derive(
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -769,6 +1573,7 @@ impl<I: ToCss> ToCss for LineNameListValue<I> {
     Clone,
     Debug,
     Default,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -805,10 +1610,33 @@ impl<I: ToCss> ToCss for LineNameList<I> {
 }
 
 /// Variants for `<grid-template-rows> | <grid-template-columns>`
+///
+/// Note: unlike most of its sibling generic types in this module (see e.g.
+/// `GenericTrackListValue`), this one doesn't derive `Animate`; `TrackList` isn't
+/// `Animate` for arbitrary `L`/`I`, only for the computed types, so `Animate`
+/// is implemented by hand on the computed `GridTemplateComponent` alias in
+/// `values::animated::grid` instead, the same way it is for `TrackList` and
+/// `TrackSize`.
+///
+/// Computing the common `None` case (the initial value) is already
+/// allocation- and recursion-free: the derived `ToComputedValue` impl below
+/// lowers to a plain `match`, and the `None` arm has no `Box<..>` field to
+/// touch, so per-element style computation for grid-less elements doesn't
+/// pay for anything beyond the match itself.
+///
+/// The derived `MallocSizeOf` impl fully accounts for the heap this type
+/// owns: it calls `size_of` on the `TrackList`/`LineNameList` field of the
+/// `TrackList`/`Subgrid` variants, `Box<T>`'s own `MallocSizeOf` impl
+/// already charges both the box's heap allocation and `T::size_of`, and
+/// `OwnedSlice<T>` (used throughout `TrackList`/`LineNameList`, including
+/// the nested per-repetition line-name slices) likewise charges its
+/// buffer's heap allocation plus each element's own `size_of` — so a large
+/// `<track-list>` or `<line-name-list>` is measured proportionally to its
+/// track/line-name count with no extra manual impl needed here.
 #[derive(
-    Animate,
     Clone,
     Debug,
+    Hash,
     MallocSizeOf,
     PartialEq,
     SpecifiedValueInfo,
@@ -824,7 +1652,6 @@ pub enum GenericGridTemplateComponent<L, I> {
     None,
     /// The grid `<track-list>`
     TrackList(
-        #[animation(field_bound)]
         #[compute(field_bound)]
         #[resolve(field_bound)]
         #[shmem(field_bound)]
@@ -832,7 +1659,6 @@ pub enum GenericGridTemplateComponent<L, I> {
     ),
     /// A `subgrid <line-name-list>?`
     /// TODO: Support animations for this after subgrid is addressed in [grid-2] spec.
-    #[animation(error)]
     Subgrid(Box<GenericLineNameList<I>>),
     /// `masonry` value.
     /// https://github.com/w3c/csswg-drafts/issues/4650
@@ -845,7 +1671,11 @@ impl<L, I> GridTemplateComponent<L, I> {
     /// The initial value.
     const INITIAL_VALUE: Self = Self::None;
 
-    /// Returns length of the <track-list>s <track-size>
+    /// Returns length of the <track-list>s <track-size>.
+    ///
+    /// `Masonry` doesn't carry a track list of its own yet (see
+    /// `is_masonry`'s doc comment), so it falls into the `0` catch-all here,
+    /// same as `None` and `Subgrid`.
     pub fn track_list_len(&self) -> usize {
         match *self {
             GridTemplateComponent::TrackList(ref tracklist) => tracklist.values.len(),
@@ -853,10 +1683,227 @@ impl<L, I> GridTemplateComponent<L, I> {
         }
     }
 
+    /// Returns true if `self` is the `masonry` keyword.
+    ///
+    /// Note: the masonry layout proposal ([issue #4650][issue]) is still
+    /// evolving, and in particular the syntax for combining `masonry` with a
+    /// `<track-list>` on the same axis isn't settled upstream yet, so
+    /// `Masonry` remains a bare keyword here rather than carrying track
+    /// configuration. The bare keyword itself already parses and serializes
+    /// (see `specified/grid.rs`'s `expect_ident_matching("masonry")` arm and
+    /// this type's derived `ToCss`); round-tripping the still-unsettled
+    /// placement syntax (e.g. combining `masonry` with a `<track-list>` on
+    /// the perpendicular axis) is explicitly out of scope until upstream
+    /// settles on one.
+    ///
+    /// [issue]: https://github.com/w3c/csswg-drafts/issues/4650
+    pub fn is_masonry(&self) -> bool {
+        matches!(*self, Self::Masonry)
+    }
+
     /// Returns true if `self` is the initial value.
     pub fn is_initial(&self) -> bool {
         matches!(*self, Self::None) // FIXME: can't use Self::INITIAL_VALUE here yet: https://github.com/rust-lang/rust/issues/66585
     }
+
+    /// Returns true if `self` is the `none` keyword.
+    pub fn is_none(&self) -> bool {
+        matches!(*self, Self::None)
+    }
+
+    /// Returns true if `self` is a `subgrid <line-name-list>?`.
+    pub fn is_subgrid(&self) -> bool {
+        matches!(*self, Self::Subgrid(..))
+    }
+
+    /// Returns the `<track-list>`, if `self` holds one.
+    pub fn as_track_list(&self) -> Option<&GenericTrackList<L, I>> {
+        match *self {
+            Self::TrackList(ref tracklist) => Some(tracklist),
+            _ => None,
+        }
+    }
+
+    /// Returns the `<line-name-list>`, if `self` is a `subgrid`.
+    pub fn as_subgrid(&self) -> Option<&GenericLineNameList<I>> {
+        match *self {
+            Self::Subgrid(ref line_name_list) => Some(line_name_list),
+            _ => None,
+        }
+    }
+
+    /// Builds a `GridTemplateComponent::TrackList`, boxing `list`. Pairs
+    /// with `as_track_list`.
+    pub fn from_track_list(list: GenericTrackList<L, I>) -> Self {
+        Self::TrackList(Box::new(list))
+    }
+
+    /// Builds a `GridTemplateComponent::Subgrid`, boxing `list`. Pairs with
+    /// `as_subgrid`.
+    pub fn from_subgrid(list: GenericLineNameList<I>) -> Self {
+        Self::Subgrid(Box::new(list))
+    }
+
+    /// Calls `f` for every `TrackSize` in this value's `<track-list>`,
+    /// including those inside `repeat()`. This centralizes a traversal
+    /// that's otherwise duplicated whenever a pass needs to touch track
+    /// sizes without caring about anything else in the list.
+    ///
+    /// This visits the `repeat()` template, not its runtime expansion: a
+    /// `repeat(3, 1fr 2fr)` visits 2 track sizes, not 6.
+    pub fn for_each_track_size(&self, f: &mut impl FnMut(&GenericTrackSize<L>)) {
+        let track_list = match *self {
+            Self::TrackList(ref list) => list,
+            _ => return,
+        };
+        for value in track_list.values.iter() {
+            match *value {
+                TrackListValue::TrackSize(ref size) => f(size),
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    for size in repeat.track_sizes.iter() {
+                        f(size);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Mutable counterpart of `for_each_track_size`.
+    pub fn for_each_track_size_mut(&mut self, f: &mut impl FnMut(&mut GenericTrackSize<L>)) {
+        let track_list = match *self {
+            Self::TrackList(ref mut list) => list,
+            _ => return,
+        };
+        for value in track_list.values.iter_mut() {
+            match *value {
+                TrackListValue::TrackSize(ref mut size) => f(size),
+                TrackListValue::TrackRepeat(ref mut repeat) => {
+                    for size in repeat.track_sizes.iter_mut() {
+                        f(size);
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<L: Clone, I: ResolvedRepeatCount> GridTemplateComponent<L, I> {
+    /// Resolves this `<grid-template-rows>`/`<grid-template-columns>` value
+    /// into a flat list of `(preceding line names, track size)` pairs,
+    /// expanding any `repeat()` (including the single `<auto-repeat>`,
+    /// which is expanded `auto_repeat_count` times) via `TrackRepeat::expand`.
+    ///
+    /// The names after a `repeat()`'s last repetition, and after the last
+    /// track, are not included.
+    ///
+    /// This is the layout-facing counterpart to `to_devtools` below: it
+    /// keeps structured `TrackSize`/`CustomIdent` values instead of
+    /// serializing them, for callers doing further computation rather than
+    /// display.
+    ///
+    /// `None`, `Subgrid`, and `Masonry` don't have an explicit track list of
+    /// their own to resolve (a subgrid inherits its parent's tracks, and
+    /// masonry has no explicit axis), so they resolve to an empty list.
+    pub fn resolve_tracks(
+        &self,
+        auto_repeat_count: usize,
+    ) -> Vec<(Vec<CustomIdent>, TrackSize<L>)> {
+        let track_list = match *self {
+            GridTemplateComponent::TrackList(ref list) => list,
+            _ => return Vec::new(),
+        };
+
+        let mut result = Vec::new();
+        let mut line_names_iter = track_list.line_names.iter();
+        for (index, value) in track_list.values.iter().enumerate() {
+            let names = line_names_iter.next().map_or_else(Vec::new, |n| n.to_vec());
+            match *value {
+                TrackListValue::TrackSize(ref size) => {
+                    let repeat_count = if index == track_list.auto_repeat_index {
+                        auto_repeat_count
+                    } else {
+                        1
+                    };
+                    for i in 0..repeat_count {
+                        let names = if i == 0 { names.clone() } else { Vec::new() };
+                        result.push((names, size.clone()));
+                    }
+                },
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    let mut tracks = repeat.expand(auto_repeat_count);
+                    if let Some(first) = tracks.first_mut() {
+                        let own = std::mem::take(&mut first.0);
+                        first.0 = names.into_iter().chain(own).collect();
+                    }
+                    result.extend(tracks);
+                },
+            }
+        }
+
+        result
+    }
+}
+
+impl<L: Clone + ToCss, I: ResolvedRepeatCount> GridTemplateComponent<L, I> {
+    /// Converts this value into a `DevToolsGridTemplate`, expanding
+    /// `<auto-repeat>` into `auto_repeat_count` tracks.
+    pub fn to_devtools(&self, auto_repeat_count: usize) -> crate::values::computed::DevToolsGridTemplate {
+        let mut result = crate::values::computed::DevToolsGridTemplate::default();
+        let track_list = match *self {
+            GridTemplateComponent::TrackList(ref list) => list,
+            _ => return result,
+        };
+
+        fn push_names(pending: &mut Vec<String>, names: &[CustomIdent]) {
+            pending.extend(names.iter().map(|n| n.to_css_string()));
+        }
+
+        // Names of the line before the next track, gathered from every
+        // adjacent `<line-names>` group until that track is pushed.
+        let mut pending = Vec::new();
+        let mut line_names_iter = track_list.line_names.iter();
+        for (index, value) in track_list.values.iter().enumerate() {
+            if let Some(names) = line_names_iter.next() {
+                push_names(&mut pending, names);
+            }
+
+            match *value {
+                TrackListValue::TrackSize(ref size) => {
+                    let repeat_count = if index == track_list.auto_repeat_index {
+                        auto_repeat_count
+                    } else {
+                        1
+                    };
+                    for _ in 0..repeat_count {
+                        result.line_names.push(std::mem::take(&mut pending));
+                        result.track_sizes.push(size.to_css_string());
+                    }
+                },
+                TrackListValue::TrackRepeat(ref repeat) => {
+                    let repeat_count = repeat.count.resolved(auto_repeat_count);
+                    for _ in 0..repeat_count {
+                        for (size, names) in
+                            repeat.track_sizes.iter().zip(repeat.line_names.iter())
+                        {
+                            push_names(&mut pending, names);
+                            result.line_names.push(std::mem::take(&mut pending));
+                            result.track_sizes.push(size.to_css_string());
+                        }
+                        if let Some(last_names) = repeat.line_names.last() {
+                            push_names(&mut pending, last_names);
+                        }
+                    }
+                },
+            }
+        }
+
+        if let Some(names) = line_names_iter.next() {
+            push_names(&mut pending, names);
+        }
+        result.line_names.push(pending);
+
+        result
+    }
 }
 
 impl<L, I> Default for GridTemplateComponent<L, I> {
@@ -865,3 +1912,572 @@ impl<L, I> Default for GridTemplateComponent<L, I> {
         Self::INITIAL_VALUE
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_line_classification_covers_five_canonical_shapes() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+
+        // `auto`
+        let auto = GridLine::<i32>::auto();
+        assert!(auto.is_auto());
+        assert!(!auto.is_ident_only());
+        assert!(!auto.is_line_number_only());
+        assert!(!auto.is_span_only());
+        assert!(!auto.is_span_ident());
+
+        // `foo`
+        let ident_only = GridLine {
+            ident: ident("foo"),
+            line_num: 0,
+            is_span: false,
+        };
+        assert!(ident_only.is_ident_only());
+        assert!(!ident_only.is_auto());
+        assert!(!ident_only.is_line_number_only());
+        assert!(!ident_only.is_span_only());
+        assert!(!ident_only.is_span_ident());
+
+        // `5`
+        let line_number_only = GridLine {
+            ident: ident(""),
+            line_num: 5,
+            is_span: false,
+        };
+        assert!(line_number_only.is_line_number_only());
+        assert!(!line_number_only.is_auto());
+        assert!(!line_number_only.is_ident_only());
+        assert!(!line_number_only.is_span_only());
+        assert!(!line_number_only.is_span_ident());
+
+        // `span 5`
+        let span_only = GridLine {
+            ident: ident(""),
+            line_num: 5,
+            is_span: true,
+        };
+        assert!(span_only.is_span_only());
+        assert!(!span_only.is_auto());
+        assert!(!span_only.is_ident_only());
+        assert!(!span_only.is_line_number_only());
+        assert!(!span_only.is_span_ident());
+
+        // `span foo`
+        let span_ident = GridLine {
+            ident: ident("foo"),
+            line_num: 0,
+            is_span: true,
+        };
+        assert!(span_ident.is_span_ident());
+        assert!(!span_ident.is_auto());
+        assert!(!span_ident.is_ident_only());
+        assert!(!span_ident.is_line_number_only());
+        assert!(!span_ident.is_span_only());
+    }
+
+    #[test]
+    fn track_breadth_as_fr() {
+        let fr = TrackBreadth::<i32>::Fr(2.0);
+        assert_eq!(fr.as_fr(), Some(2.0));
+        assert!(fr.is_fr());
+
+        let auto = TrackBreadth::<i32>::Auto;
+        assert_eq!(auto.as_fr(), None);
+        assert!(!auto.is_fr());
+    }
+
+    #[test]
+    fn track_breadth_map() {
+        let breadth = TrackBreadth::<i32>::Breadth(10);
+        assert_eq!(breadth.map(|v| v.to_string()), TrackBreadth::Breadth("10".to_owned()));
+
+        assert_eq!(TrackBreadth::<i32>::Fr(1.5).map(|v: &i32| *v), TrackBreadth::Fr(1.5));
+        assert_eq!(TrackBreadth::<i32>::Auto.map(|v: &i32| *v), TrackBreadth::Auto);
+        assert_eq!(
+            TrackBreadth::<i32>::MinContent.map(|v: &i32| *v),
+            TrackBreadth::MinContent
+        );
+        assert_eq!(
+            TrackBreadth::<i32>::MaxContent.map(|v: &i32| *v),
+            TrackBreadth::MaxContent
+        );
+    }
+
+    #[test]
+    fn track_size_map() {
+        let breadth = TrackSize::Breadth(TrackBreadth::<i32>::Breadth(10));
+        assert_eq!(
+            breadth.map(|v: &i32| v.to_string()),
+            TrackSize::Breadth(TrackBreadth::Breadth("10".to_owned()))
+        );
+
+        let fit_content = TrackSize::FitContent(TrackBreadth::<i32>::Breadth(5));
+        assert_eq!(
+            fit_content.map(|v: &i32| v.to_string()),
+            TrackSize::FitContent(TrackBreadth::Breadth("5".to_owned()))
+        );
+
+        // The flexible side of a `minmax()` must pass through `map` unchanged,
+        // since `Fr` doesn't carry an `L` payload to map over.
+        let minmax = TrackSize::Minmax(TrackBreadth::<i32>::Fr(1.0), TrackBreadth::Breadth(20));
+        assert_eq!(
+            minmax.map(|v: &i32| v.to_string()),
+            TrackSize::Minmax(TrackBreadth::Fr(1.0), TrackBreadth::Breadth("20".to_owned()))
+        );
+    }
+
+    #[test]
+    fn track_size_min_max_breadth() {
+        let breadth = TrackSize::Breadth(TrackBreadth::<i32>::Fr(1.0));
+        assert_eq!(*breadth.min_breadth(), TrackBreadth::Fr(1.0));
+        assert_eq!(*breadth.max_breadth(), TrackBreadth::Fr(1.0));
+
+        let minmax = TrackSize::Minmax(TrackBreadth::<i32>::Auto, TrackBreadth::Fr(1.0));
+        assert_eq!(*minmax.min_breadth(), TrackBreadth::Auto);
+        assert_eq!(*minmax.max_breadth(), TrackBreadth::Fr(1.0));
+
+        let fit_content = TrackSize::FitContent(TrackBreadth::<i32>::Breadth(10));
+        assert_eq!(*fit_content.min_breadth(), TrackBreadth::Auto);
+        assert_eq!(*fit_content.max_breadth(), TrackBreadth::Breadth(10));
+    }
+
+    #[test]
+    fn track_size_is_flexible_and_content_sized() {
+        let fixed = TrackSize::Breadth(TrackBreadth::<i32>::Breadth(10));
+        assert!(fixed.is_fixed());
+        assert!(!fixed.is_flexible());
+        assert!(!fixed.is_content_sized());
+
+        let flexible = TrackSize::Breadth(TrackBreadth::<i32>::Fr(1.0));
+        assert!(!flexible.is_fixed());
+        assert!(flexible.is_flexible());
+        assert!(!flexible.is_content_sized());
+
+        let auto = TrackSize::Breadth(TrackBreadth::<i32>::Auto);
+        assert!(!auto.is_fixed());
+        assert!(!auto.is_flexible());
+        assert!(auto.is_content_sized());
+
+        let fit_content = TrackSize::FitContent(TrackBreadth::<i32>::Breadth(100));
+        assert!(!fit_content.is_fixed());
+        assert!(!fit_content.is_flexible());
+        assert!(fit_content.is_content_sized());
+
+        let minmax_fixed_flexible =
+            TrackSize::Minmax(TrackBreadth::<i32>::Breadth(10), TrackBreadth::Fr(1.0));
+        assert!(minmax_fixed_flexible.is_fixed());
+        assert!(minmax_fixed_flexible.is_flexible());
+    }
+
+    #[test]
+    fn implicit_grid_tracks_track_for_cycles() {
+        let tracks = ImplicitGridTracks::from_tracks(vec![10i32, 20i32]);
+        assert_eq!(tracks.track_for(0), 10);
+        assert_eq!(tracks.track_for(1), 20);
+        assert_eq!(tracks.track_for(2), 10);
+        assert_eq!(tracks.track_for(3), 20);
+
+        let auto = ImplicitGridTracks::<i32>::default();
+        assert_eq!(auto.track_for(0), i32::default());
+        assert_eq!(auto.track_for(5), i32::default());
+    }
+
+    #[test]
+    fn name_repeat_number() {
+        let numeric = NameRepeat {
+            count: RepeatCount::Number(3i32),
+            line_names: vec![vec![].into()].into(),
+        };
+        assert!(!numeric.is_auto_fill());
+        assert_eq!(numeric.number(), Some(&3));
+
+        let auto_fill = NameRepeat {
+            count: RepeatCount::<i32>::AutoFill,
+            line_names: vec![vec![].into()].into(),
+        };
+        assert!(auto_fill.is_auto_fill());
+        assert_eq!(auto_fill.number(), None);
+    }
+
+    #[test]
+    fn track_repeat_assert_invariants_accepts_correct_shape() {
+        // 2 <track-size> values, 3 <line-names> groups: satisfies the N+1 rule.
+        let repeat = TrackRepeat {
+            count: RepeatCount::Number(2i32),
+            line_names: vec![vec![].into(), vec![].into(), vec![].into()].into(),
+            track_sizes: vec![
+                TrackSize::Breadth(TrackBreadth::<i32>::Fr(1.0)),
+                TrackSize::Breadth(TrackBreadth::<i32>::Fr(2.0)),
+            ]
+            .into(),
+        };
+        repeat.assert_invariants();
+    }
+
+    #[test]
+    fn repeat_count_is_auto_and_as_number() {
+        let number = RepeatCount::Number(5i32);
+        assert!(!number.is_auto());
+        assert_eq!(number.as_number(), Some(&5));
+
+        let auto_fill = RepeatCount::<i32>::AutoFill;
+        assert!(auto_fill.is_auto());
+        assert_eq!(auto_fill.as_number(), None);
+
+        let auto_fit = RepeatCount::<i32>::AutoFit;
+        assert!(auto_fit.is_auto());
+        assert_eq!(auto_fit.as_number(), None);
+    }
+
+    #[test]
+    fn track_list_is_auto_serializes_like_grid_template_component_none() {
+        let empty = TrackList::<i32, i32> {
+            auto_repeat_index: usize::MAX,
+            values: vec![].into(),
+            line_names: vec![vec![].into()].into(),
+        };
+        assert!(empty.is_auto());
+        assert_eq!(
+            empty.to_css_string(),
+            GridTemplateComponent::<i32, i32>::None.to_css_string()
+        );
+
+        let track = TrackListValue::<i32, i32>::TrackSize(TrackSize::Breadth(TrackBreadth::Fr(1.0)));
+        let non_empty = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![track].into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        };
+        assert!(!non_empty.is_auto());
+    }
+
+    #[test]
+    fn track_list_iter_tracks_with_line_names() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let track = |fr| TrackListValue::<i32, i32>::TrackSize(TrackSize::Breadth(TrackBreadth::Fr(fr)));
+
+        // [a] 1fr [b] 2fr [c]
+        let track_list = TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![track(1.0), track(2.0)].into(),
+            line_names: vec![
+                vec![ident("a")].into(),
+                vec![ident("b")].into(),
+                vec![ident("c")].into(),
+            ]
+            .into(),
+        };
+
+        let tuples: Vec<_> = track_list.iter_tracks_with_line_names().collect();
+        assert_eq!(tuples.len(), 3);
+        assert_eq!(tuples[0].0, &vec![ident("a")].into());
+        assert_eq!(tuples[0].1, Some(&track(1.0)));
+        assert_eq!(tuples[1].0, &vec![ident("b")].into());
+        assert_eq!(tuples[1].1, Some(&track(2.0)));
+        assert_eq!(tuples[2].0, &vec![ident("c")].into());
+        assert_eq!(tuples[2].1, None);
+    }
+
+    #[test]
+    fn track_list_line_name_count_and_subgrid_fits() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let track = |fr| TrackListValue::<i32, i32>::TrackSize(TrackSize::Breadth(TrackBreadth::Fr(fr)));
+        let repeat = TrackListValue::<i32, i32>::TrackRepeat(TrackRepeat {
+            count: RepeatCount::AutoFill,
+            track_sizes: vec![TrackSize::Breadth(TrackBreadth::Breadth(100))].into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        });
+
+        // [a] 1fr repeat(auto-fill, 100px) 2fr [b]
+        let track_list = TrackList {
+            auto_repeat_index: 1,
+            values: vec![track(1.0), repeat, track(2.0)].into(),
+            line_names: vec![
+                vec![ident("a")].into(),
+                vec![].into(),
+                vec![].into(),
+                vec![ident("b")].into(),
+            ]
+            .into(),
+        };
+
+        // 4 <line-names> positions for the 3 entries in `values` (explicit
+        // and auto-repeat sections alike), regardless of how many tracks the
+        // auto-repeat eventually expands to at layout time.
+        assert_eq!(track_list.line_name_count(), 4);
+
+        assert!(!track_list.subgrid_fits(2));
+        assert!(track_list.subgrid_fits(3));
+        assert!(track_list.subgrid_fits(4));
+    }
+
+    #[test]
+    fn track_repeat_names_and_sizes_per_repetition() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+
+        // repeat(3, [a] 1fr [b] 2fr [c]): two tracks, three interleaved
+        // <line-names> groups.
+        let repeat = TrackRepeat::<i32, i32> {
+            count: RepeatCount::Number(3),
+            line_names: vec![
+                vec![ident("a")].into(),
+                vec![ident("b")].into(),
+                vec![ident("c")].into(),
+            ]
+            .into(),
+            track_sizes: vec![
+                TrackSize::Breadth(TrackBreadth::Fr(1.0)),
+                TrackSize::Breadth(TrackBreadth::Fr(2.0)),
+            ]
+            .into(),
+        };
+
+        assert_eq!(repeat.line_names_per_repetition(), 3);
+        assert_eq!(repeat.track_sizes_per_repetition(), 2);
+    }
+
+    #[test]
+    fn grid_template_component_is_masonry() {
+        let masonry = GridTemplateComponent::<i32, i32>::Masonry;
+        assert!(masonry.is_masonry());
+        assert_eq!(masonry.track_list_len(), 0);
+
+        let none = GridTemplateComponent::<i32, i32>::None;
+        assert!(!none.is_masonry());
+    }
+
+    #[test]
+    fn grid_template_component_masonry_serializes_as_bare_keyword() {
+        let masonry = GridTemplateComponent::<i32, i32>::Masonry;
+        assert_eq!(masonry.to_css_string(), "masonry");
+    }
+
+    #[test]
+    fn grid_template_component_accessors() {
+        let none = GridTemplateComponent::<i32, i32>::None;
+        assert!(none.is_none());
+        assert!(!none.is_subgrid());
+        assert!(none.as_track_list().is_none());
+        assert!(none.as_subgrid().is_none());
+
+        let track_list = GridTemplateComponent::TrackList(Box::new(TrackList {
+            auto_repeat_index: usize::MAX,
+            values: crate::OwnedSlice::default(),
+            line_names: vec![crate::OwnedSlice::default()].into(),
+        }));
+        assert!(!track_list.is_none());
+        assert!(!track_list.is_subgrid());
+        assert!(track_list.as_track_list().is_some());
+        assert!(track_list.as_subgrid().is_none());
+
+        let subgrid = GridTemplateComponent::<i32, i32>::Subgrid(Box::new(LineNameList {
+            expanded_line_names_length: 0,
+            line_names: crate::OwnedSlice::default(),
+        }));
+        assert!(!subgrid.is_none());
+        assert!(subgrid.is_subgrid());
+        assert!(subgrid.as_track_list().is_none());
+        assert!(subgrid.as_subgrid().is_some());
+
+        let masonry = GridTemplateComponent::<i32, i32>::Masonry;
+        assert!(!masonry.is_none());
+        assert!(!masonry.is_subgrid());
+        assert!(masonry.as_track_list().is_none());
+        assert!(masonry.as_subgrid().is_none());
+    }
+
+    fn hash_of<T: std::hash::Hash>(value: &T) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn track_list_structurally_equal_hashes_equal() {
+        use crate::Atom;
+
+        let ident = |s: &'static str| CustomIdent(Atom::from(s));
+        let make = || TrackList::<i32, i32> {
+            auto_repeat_index: usize::MAX,
+            values: vec![TrackListValue::TrackSize(TrackSize::Breadth(
+                TrackBreadth::Fr(1.0),
+            ))]
+            .into(),
+            line_names: vec![vec![ident("a")].into(), vec![].into()].into(),
+        };
+
+        assert_eq!(make(), make());
+        assert_eq!(hash_of(&make()), hash_of(&make()));
+    }
+
+    #[test]
+    fn track_size_minmax_fr_hashes_differ() {
+        let one_fr = TrackSize::Minmax(TrackBreadth::<i32>::Breadth(100), TrackBreadth::Fr(1.0));
+        let two_fr = TrackSize::Minmax(TrackBreadth::<i32>::Breadth(100), TrackBreadth::Fr(2.0));
+
+        assert_ne!(one_fr, two_fr);
+        assert_ne!(hash_of(&one_fr), hash_of(&two_fr));
+    }
+
+    fn css_of_grid_area(
+        row_start: &GridLine<specified::Integer>,
+        column_start: &GridLine<specified::Integer>,
+        row_end: &GridLine<specified::Integer>,
+        column_end: &GridLine<specified::Integer>,
+    ) -> String {
+        let mut css = String::new();
+        serialize_grid_area(
+            row_start,
+            column_start,
+            row_end,
+            column_end,
+            &mut CssWriter::new(&mut css),
+        )
+        .unwrap();
+        css
+    }
+
+    #[test]
+    fn serialize_grid_area_collapses_all_matching_lines() {
+        let line = |n: i32| GridLine::<specified::Integer> {
+            ident: CustomIdent(atom!("")),
+            line_num: specified::Integer::new(n),
+            is_span: false,
+        };
+
+        // `1 / 1 / 1 / 1` collapses all the way down to `1`.
+        let one = line(1);
+        assert_eq!(css_of_grid_area(&one, &one, &one, &one), "1");
+    }
+
+    #[test]
+    fn serialize_grid_area_collapses_matching_named_lines() {
+        use crate::Atom;
+
+        let named = |s: &'static str| GridLine::<specified::Integer> {
+            ident: CustomIdent(Atom::from(s)),
+            line_num: specified::Integer::new(0),
+            is_span: false,
+        };
+
+        // `a / a / a / a` (all four lines the same <custom-ident>) collapses to `a`.
+        let a = named("a");
+        assert_eq!(css_of_grid_area(&a, &a, &a, &a), "a");
+    }
+
+    #[test]
+    fn serialize_grid_area_keeps_distinct_lines() {
+        let line = |n: i32| GridLine::<specified::Integer> {
+            ident: CustomIdent(atom!("")),
+            line_num: specified::Integer::new(n),
+            is_span: false,
+        };
+
+        assert_eq!(
+            css_of_grid_area(&line(1), &line(2), &line(3), &line(4)),
+            "1 / 2 / 3 / 4"
+        );
+        // The trailing `grid-column-end` is omittable on its own even when
+        // the rest of the lines differ.
+        let auto = GridLine::<specified::Integer> {
+            ident: CustomIdent(atom!("")),
+            line_num: specified::Integer::new(0),
+            is_span: false,
+        };
+        assert_eq!(
+            css_of_grid_area(&line(1), &line(2), &line(3), &auto),
+            "1 / 2 / 3"
+        );
+    }
+
+    #[test]
+    fn grid_template_component_for_each_track_size_visits_template_not_expansion() {
+        let track = |fr| TrackListValue::<i32, i32>::TrackSize(TrackSize::Breadth(TrackBreadth::Fr(fr)));
+        let repeat = TrackListValue::<i32, i32>::TrackRepeat(TrackRepeat {
+            count: RepeatCount::Number(3),
+            line_names: vec![vec![].into(), vec![].into(), vec![].into()].into(),
+            track_sizes: vec![
+                TrackSize::Breadth(TrackBreadth::Fr(1.0)),
+                TrackSize::Breadth(TrackBreadth::Fr(2.0)),
+            ]
+            .into(),
+        });
+
+        // `1fr repeat(3, 1fr 2fr)`: visiting the template, not the runtime
+        // expansion, counts 3 track sizes (the leading `1fr` plus the two in
+        // the repeat's own template), not 7 (1 + 3 * 2).
+        let component = GridTemplateComponent::<i32, i32>::TrackList(Box::new(TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![track(1.0), repeat].into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        }));
+
+        let mut count = 0;
+        component.for_each_track_size(&mut |_| count += 1);
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn grid_template_component_for_each_track_size_mut_scales_breadths() {
+        let repeat = TrackListValue::<i32, i32>::TrackRepeat(TrackRepeat {
+            count: RepeatCount::Number(3),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+            track_sizes: vec![TrackSize::Breadth(TrackBreadth::Breadth(10))].into(),
+        });
+        let track = TrackListValue::<i32, i32>::TrackSize(TrackSize::Breadth(TrackBreadth::Breadth(5)));
+
+        let mut component = GridTemplateComponent::<i32, i32>::TrackList(Box::new(TrackList {
+            auto_repeat_index: usize::MAX,
+            values: vec![track, repeat].into(),
+            line_names: vec![vec![].into(), vec![].into()].into(),
+        }));
+
+        component.for_each_track_size_mut(&mut |size| {
+            if let TrackSize::Breadth(TrackBreadth::Breadth(ref mut v)) = *size {
+                *v *= 2;
+            }
+        });
+
+        let mut doubled = Vec::new();
+        component.for_each_track_size(&mut |size| {
+            if let TrackSize::Breadth(TrackBreadth::Breadth(v)) = *size {
+                doubled.push(v);
+            }
+        });
+        assert_eq!(doubled, vec![10, 20]);
+    }
+
+    #[test]
+    fn grid_template_component_from_track_list_and_from_subgrid() {
+        let list = TrackList::<i32, i32> {
+            auto_repeat_index: usize::MAX,
+            values: vec![].into(),
+            line_names: vec![vec![].into()].into(),
+        };
+        let component = GridTemplateComponent::<i32, i32>::from_track_list(list.clone());
+        assert_eq!(component.as_track_list(), Some(&list));
+        assert!(component.as_subgrid().is_none());
+
+        let names = LineNameList::<i32> {
+            expanded_line_names_length: 0,
+            line_names: vec![].into(),
+        };
+        let component = GridTemplateComponent::<i32, i32>::from_subgrid(names.clone());
+        assert_eq!(component.as_subgrid(), Some(&names));
+        assert!(component.as_track_list().is_none());
+    }
+}
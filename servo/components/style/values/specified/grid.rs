@@ -9,12 +9,115 @@ use crate::parser::{Parse, ParserContext};
 use crate::values::generics::grid::{GridTemplateComponent, ImplicitGridTracks, RepeatCount};
 use crate::values::generics::grid::{LineNameList, LineNameListValue, NameRepeat, TrackBreadth};
 use crate::values::generics::grid::{TrackList, TrackListValue, TrackRepeat, TrackSize};
-use crate::values::specified::{Integer, LengthPercentage};
+use crate::values::generics::grid::{MAX_GRID_LINE, MIN_GRID_LINE};
+use crate::values::specified::{GridLine, Integer, LengthPercentage};
 use crate::values::{CSSFloat, CustomIdent};
+use crate::Zero;
 use cssparser::{Parser, Token};
 use std::mem;
 use style_traits::{ParseError, StyleParseErrorKind};
 
+impl GridLine {
+    /// Creates a `span <n>` grid line, clamping `n` to
+    /// `[MIN_GRID_LINE, MAX_GRID_LINE]`.
+    ///
+    /// `n` must be non-zero; spec-wise `span 0` is never valid.
+    pub fn span(n: Integer) -> Self {
+        debug_assert!(!n.is_zero(), "span 0 is not a valid grid line");
+        Self {
+            is_span: true,
+            line_num: n.clamp(MIN_GRID_LINE, MAX_GRID_LINE),
+            ident: CustomIdent(atom!("")),
+        }
+    }
+
+    /// Creates a plain `<integer>` grid line, clamping `n` to
+    /// `[MIN_GRID_LINE, MAX_GRID_LINE]`.
+    pub fn from_line(n: Integer) -> Self {
+        Self {
+            is_span: false,
+            line_num: n.clamp(MIN_GRID_LINE, MAX_GRID_LINE),
+            ident: CustomIdent(atom!("")),
+        }
+    }
+
+    /// Creates a `<custom-ident>`-only grid line.
+    pub fn named(ident: CustomIdent) -> Self {
+        Self {
+            is_span: false,
+            line_num: Integer::new(0),
+            ident,
+        }
+    }
+
+    /// Returns `line_num`'s value already clamped to
+    /// `[MIN_GRID_LINE, MAX_GRID_LINE]`, matching what `GridLine::parse`
+    /// stores. `line_num` is always in range once parsed or constructed
+    /// through `span`/`from_line`, so this is a thin accessor rather than a
+    /// re-clamp.
+    pub fn clamped_line_num(&self) -> i32 {
+        self.line_num.value().clamp(MIN_GRID_LINE, MAX_GRID_LINE)
+    }
+
+    /// Returns a copy of `self` with `line_num` re-clamped to
+    /// `[MIN_GRID_LINE, MAX_GRID_LINE]`.
+    ///
+    /// `GridLine`'s own constructors (`span`, `from_line`, `parse`) always
+    /// produce an already-clamped value, so this is a no-op for any
+    /// `GridLine` obtained through them. It only matters for a `GridLine`
+    /// built via a struct literal that skipped that clamp: `derive(PartialEq)`
+    /// compares `line_num` verbatim, so two grid lines that are equivalent
+    /// per spec (e.g. `10001` and `MAX_GRID_LINE`) only compare equal once
+    /// both are normalized first.
+    pub fn normalized(&self) -> Self {
+        Self {
+            is_span: self.is_span,
+            line_num: self.line_num.clamp(MIN_GRID_LINE, MAX_GRID_LINE),
+            ident: self.ident.clone(),
+        }
+    }
+
+    /// A sentinel returned by `resolve_against` for a placement it can't
+    /// resolve to a concrete line number on its own: `auto`, or a
+    /// `<custom-ident>` placement (naming a line requires looking it up in
+    /// the grid's named lines, which this method has no access to). `0` is
+    /// never a valid (1-based) grid line number, so it's safe to use here.
+    pub const UNRESOLVED_LINE: i32 = 0;
+
+    /// Resolves this `<grid-line>` to a concrete, one-based line number
+    /// given `start` (the line this placement is relative to) and
+    /// `explicit_line_count` (the number of lines in the explicit grid, used
+    /// to count negative line numbers back from the end).
+    ///
+    /// This only performs the numeric part of placement resolution
+    /// (<https://drafts.csswg.org/css-grid/#line-placement>); a
+    /// `<custom-ident>` placement returns `UNRESOLVED_LINE` since resolving
+    /// it requires the grid's named-lines table, which callers must consult
+    /// themselves.
+    pub fn resolve_against(&self, start: i32, explicit_line_count: i32) -> i32 {
+        if self.is_span {
+            return start + self.line_num.value();
+        }
+
+        if self.ident.0 != atom!("") {
+            return Self::UNRESOLVED_LINE;
+        }
+
+        let line_num = self.line_num.value();
+        if line_num == 0 {
+            // `auto`.
+            return Self::UNRESOLVED_LINE;
+        }
+
+        if line_num > 0 {
+            line_num
+        } else {
+            // Negative lines count backwards from the last explicit line.
+            explicit_line_count + 1 + line_num
+        }
+    }
+}
+
 /// Parse a single flexible length.
 pub fn parse_flex<'i, 't>(input: &mut Parser<'i, 't>) -> Result<CSSFloat, ParseError<'i>> {
     let location = input.current_source_location();
@@ -85,10 +188,9 @@ impl Parse for TrackSize<LengthPercentage> {
                     };
 
                 input.expect_comma()?;
-                Ok(TrackSize::Minmax(
-                    inflexible_breadth,
-                    TrackBreadth::parse(context, input)?,
-                ))
+                let track_size = TrackSize::Minmax(inflexible_breadth, TrackBreadth::parse(context, input)?);
+                track_size.assert_invariants();
+                Ok(track_size)
             });
         }
 
@@ -170,6 +272,24 @@ impl TrackRepeat<LengthPercentage, Integer> {
 
                     loop {
                         current_names = input.try_parse(parse_line_names).unwrap_or_default();
+
+                        // `repeat()` cannot nest: `<track-size>` (what we're
+                        // about to try to parse) has no `repeat()` variant,
+                        // so this would fail as a generic "expected a
+                        // <track-size>" error below anyway, but calling it
+                        // out specifically here gives devtools a much more
+                        // actionable message for this common mistake.
+                        if input
+                            .try_parse(|i| i.expect_function_matching("repeat").map_err(|e| e.into()))
+                            .is_ok()
+                        {
+                            return Err(
+                                input.new_custom_error(StyleParseErrorKind::UnexpectedFunction(
+                                    "repeat".into(),
+                                )),
+                            );
+                        }
+
                         if let Ok(track_size) = input.try_parse(|i| TrackSize::parse(context, i)) {
                             if !track_size.is_fixed() {
                                 if is_auto {
@@ -187,7 +307,19 @@ impl TrackRepeat<LengthPercentage, Integer> {
                             names.push(current_names);
                         } else {
                             if values.is_empty() {
-                                // expecting at least one <track-size>
+                                // A `repeat()` with only `<line-names>` and no
+                                // `<track-size>` at all, e.g. `repeat(2, [a]
+                                // [b])`, is the `<name-repeat>` production,
+                                // not `<track-repeat>`/`<fixed-repeat>` — it's
+                                // only valid inside a `<line-name-list>` for
+                                // `subgrid` (see `NameRepeat` and
+                                // `LineNameListValue::Repeat`), which parses
+                                // through a completely separate code path
+                                // from this one. A `TrackRepeat` always needs
+                                // at least one `<track-size>` to repeat, so
+                                // this is a deliberate rejection, not a gap:
+                                // there's no `<track-size>`-less shape for
+                                // this type to represent.
                                 return Err(
                                     input.new_custom_error(StyleParseErrorKind::UnspecifiedError)
                                 );
@@ -203,6 +335,7 @@ impl TrackRepeat<LengthPercentage, Integer> {
                         track_sizes: values.into(),
                         line_names: names.into(),
                     };
+                    repeat.assert_invariants();
 
                     Ok((repeat, repeat_type))
                 })
@@ -211,6 +344,13 @@ impl TrackRepeat<LengthPercentage, Integer> {
 }
 
 impl Parse for TrackList<LengthPercentage, Integer> {
+    /// Parses interleaved `[<line-names>]` and `<track-size> | <track-repeat>`
+    /// values, maintaining the N+1 `line_names`-to-`values` invariant
+    /// documented on `GenericTrackList`, and recording the position of the
+    /// sole `<auto-repeat>` (if any) in `auto_repeat_index` --- left as
+    /// `usize::MAX`, an always-out-of-bounds sentinel, when there is none.
+    /// A second `<auto-repeat>`, or a flexible/intrinsic size alongside one,
+    /// is rejected per the `<auto-track-list>` grammar.
     fn parse<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
@@ -405,6 +545,37 @@ impl LineNameListValue<Integer> {
     }
 }
 
+impl LineNameList<Integer> {
+    /// Recomputes `expanded_line_names_length` from `line_names`.
+    ///
+    /// `LineNameList::parse` computes this length inline as it parses (see
+    /// below), so this is only needed for a `LineNameList` built or edited
+    /// some other way, to bring `expanded_line_names_length` back in sync.
+    pub fn recompute_expanded_length(&mut self) {
+        self.expanded_line_names_length = self
+            .line_names
+            .iter()
+            .map(|value| value.line_names_length())
+            .sum();
+    }
+
+    /// Debug-only check that `expanded_line_names_length` matches what
+    /// `recompute_expanded_length` would produce. A stale count here
+    /// corrupts `<line-name-list>` expansion (callers use it to size the
+    /// expanded line name list up front), so this catches the mistake at
+    /// the point the list was left inconsistent rather than downstream.
+    pub fn validate(&self) {
+        debug_assert_eq!(
+            self.expanded_line_names_length,
+            self.line_names
+                .iter()
+                .map(|value| value.line_names_length())
+                .sum::<usize>(),
+            "LineNameList::expanded_line_names_length is stale"
+        );
+    }
+}
+
 impl Parse for LineNameList<Integer> {
     fn parse<'i, 't>(
         context: &ParserContext,
@@ -10,7 +10,8 @@ use super::computed::transform::DirectionVector;
 use super::computed::{Context, ToComputedValue};
 use super::generics::grid::ImplicitGridTracks as GenericImplicitGridTracks;
 use super::generics::grid::{GridLine as GenericGridLine, TrackBreadth as GenericTrackBreadth};
-use super::generics::grid::{TrackList as GenericTrackList, TrackSize as GenericTrackSize};
+use super::generics::grid::{LineNameList as GenericLineNameList, TrackList as GenericTrackList};
+use super::generics::grid::TrackSize as GenericTrackSize;
 use super::generics::transform::IsParallelTo;
 use super::generics::{self, GreaterThanOrEqualToOne, NonNegative};
 use super::{CSSFloat, CSSInteger};
@@ -635,6 +636,16 @@ impl Integer {
             was_calc: true,
         }
     }
+
+    /// Returns a new `Integer` with the same value clamped to
+    /// `[min, max]`, preserving whether it came from a `calc()` expression
+    /// so that it still round-trips through `to_css` as `calc(...)`.
+    pub fn clamp(self, min: CSSInteger, max: CSSInteger) -> Self {
+        Integer {
+            value: self.value.max(min).min(max),
+            was_calc: self.was_calc,
+        }
+    }
 }
 
 impl Parse for Integer {
@@ -754,6 +765,9 @@ pub type TrackList = GenericTrackList<LengthPercentage, Integer>;
 /// The specified value of a `<grid-line>`.
 pub type GridLine = GenericGridLine<Integer>;
 
+/// The specified value of a subgrid `<line-name-list>`.
+pub type LineNameList = GenericLineNameList<Integer>;
+
 /// `<grid-template-rows> | <grid-template-columns>`
 pub type GridTemplateComponent = GenericGridTemplateComponent<LengthPercentage, Integer>;
 
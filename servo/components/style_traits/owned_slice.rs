@@ -78,6 +78,12 @@ impl<T: PartialEq> PartialEq for OwnedSlice<T> {
 
 impl<T: Eq> Eq for OwnedSlice<T> {}
 
+impl<T: std::hash::Hash> std::hash::Hash for OwnedSlice<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state);
+    }
+}
+
 impl<T: Sized> OwnedSlice<T> {
     /// Convert the OwnedSlice into a boxed slice.
     #[inline]
@@ -4,7 +4,7 @@
 
 use cssparser::RGBA;
 use style::values::animated::{Animate, Procedure, ToAnimatedValue};
-use style::values::computed::Percentage;
+use style::values::computed::{GridLine, Percentage};
 use style::values::generics::transform::{Transform, TransformOperation};
 
 fn interpolate_rgba(from: RGBA, to: RGBA, progress: f64) -> RGBA {
@@ -167,3 +167,77 @@ fn test_transform_interpolation_on_mismatched_lists() {
         }])
     );
 }
+
+// Grid
+fn grid_line(line_num: i32) -> GridLine {
+    use style::values::CustomIdent;
+    use style::Atom;
+
+    GridLine {
+        ident: CustomIdent(Atom::from("")),
+        line_num,
+        is_span: false,
+    }
+}
+
+#[test]
+fn test_grid_line_interpolation_numeric() {
+    let from = grid_line(2);
+    let to = grid_line(4);
+    assert_eq!(
+        from.animate(&to, Procedure::Interpolate { progress: 0.5 }).unwrap(),
+        grid_line(3)
+    );
+}
+
+#[test]
+fn test_grid_line_interpolation_rejects_span_mismatch() {
+    let from = grid_line(2);
+    let mut to = grid_line(2);
+    to.is_span = true;
+    assert!(from.animate(&to, Procedure::Interpolate { progress: 0.5 }).is_err());
+}
+
+#[test]
+fn test_grid_template_component_masonry_animates_as_discrete() {
+    use style::values::computed::GridTemplateComponent;
+
+    let masonry = GridTemplateComponent::Masonry;
+    let none = GridTemplateComponent::None;
+    let procedure = Procedure::Interpolate { progress: 0.5 };
+
+    assert_eq!(masonry.animate(&masonry, procedure), Ok(GridTemplateComponent::Masonry));
+    // `Masonry` has no `<track-list>` to interpolate, so a mismatch with
+    // `None` (or any other variant) flips discretely rather than erroring,
+    // the same as `Subgrid` pairings below.
+    assert_eq!(masonry.animate(&none, procedure), Ok(none.clone()));
+}
+
+#[test]
+fn test_grid_template_component_subgrid_animates_as_discrete() {
+    use style::values::computed::GridTemplateComponent;
+    use style::values::generics::grid::{LineNameList, LineNameListValue};
+    use style::values::CustomIdent;
+    use style::Atom;
+
+    fn subgrid(name: &'static str) -> GridTemplateComponent {
+        let names: style::OwnedSlice<CustomIdent> = vec![CustomIdent(Atom::from(name))].into();
+        GridTemplateComponent::Subgrid(Box::new(LineNameList {
+            expanded_line_names_length: 1,
+            line_names: vec![LineNameListValue::LineNames(names)].into(),
+        }))
+    }
+
+    let a = subgrid("a");
+    let b = subgrid("b");
+    let procedure = Procedure::Interpolate { progress: 0.5 };
+
+    // Two mismatched subgrids flip discretely rather than erroring.
+    assert_eq!(a.animate(&b, procedure), Ok(b.clone()));
+    assert_eq!(a.animate(&a, procedure), Ok(a.clone()));
+
+    // A subgrid and `none` also have no defined interpolation between
+    // them, so they flip discretely rather than erroring, too.
+    let none = GridTemplateComponent::None;
+    assert_eq!(a.animate(&none, procedure), Ok(none.clone()));
+}
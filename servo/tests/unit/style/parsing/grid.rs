@@ -0,0 +1,720 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use parsing::parse_entirely;
+use style::parser::Parse;
+use style::values::specified::{GridLine, ImplicitGridTracks, Integer};
+use style_traits::ToCss;
+
+// Exercises every valid permutation of the `<grid-line>` grammar
+// (<https://drafts.csswg.org/css-grid/#typedef-grid-row-start-grid-line>) to
+// prove that refactoring the component-consumption loop in `GridLine::parse`
+// didn't change acceptance behavior.
+#[test]
+fn test_grid_line() {
+    // A single component, on its own.
+    assert!(parse_entirely(GridLine::parse, "auto").unwrap().is_auto());
+    assert_eq!(
+        parse_entirely(GridLine::parse, "foo")
+            .unwrap()
+            .to_css_string(),
+        "foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "5")
+            .unwrap()
+            .to_css_string(),
+        "5"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span 5")
+            .unwrap()
+            .to_css_string(),
+        "span 5"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span foo")
+            .unwrap()
+            .to_css_string(),
+        "span foo"
+    );
+
+    // integer && ident, in both orders.
+    assert_eq!(
+        parse_entirely(GridLine::parse, "5 foo")
+            .unwrap()
+            .to_css_string(),
+        "5 foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "foo 5")
+            .unwrap()
+            .to_css_string(),
+        "5 foo"
+    );
+
+    // span must come first or last, with the other two components
+    // sandwiched in either order.
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span 5 foo")
+            .unwrap()
+            .to_css_string(),
+        "span 5 foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span foo 5")
+            .unwrap()
+            .to_css_string(),
+        "span 5 foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "5 foo span")
+            .unwrap()
+            .to_css_string(),
+        "span 5 foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "foo 5 span")
+            .unwrap()
+            .to_css_string(),
+        "span 5 foo"
+    );
+
+    // Each component may only be consumed once.
+    assert!(parse_entirely(GridLine::parse, "span span").is_err());
+    assert!(parse_entirely(GridLine::parse, "5 5").is_err());
+    assert!(parse_entirely(GridLine::parse, "foo foo").is_err());
+    // `span` sandwiched between the other two is invalid.
+    assert!(parse_entirely(GridLine::parse, "5 span foo").is_err());
+    assert!(parse_entirely(GridLine::parse, "foo span 5").is_err());
+    // `auto` isn't a valid <custom-ident> here.
+    assert!(parse_entirely(GridLine::parse, "auto 5").is_err());
+    // Zero is not a valid line number.
+    assert!(parse_entirely(GridLine::parse, "0").is_err());
+    // Nothing to parse at all.
+    assert!(parse_entirely(GridLine::parse, "").is_err());
+}
+
+#[test]
+fn test_grid_line_calc() {
+    // A calc() line number still round-trips as calc(), rather than losing
+    // that context when the value is clamped to the grid line bounds.
+    assert_eq!(
+        parse_entirely(GridLine::parse, "calc(2 + 1)")
+            .unwrap()
+            .to_css_string(),
+        "calc(3)"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span calc(2 + 1)")
+            .unwrap()
+            .to_css_string(),
+        "span calc(3)"
+    );
+    // A calc() that evaluates to zero is still invalid, same as a literal 0.
+    assert!(parse_entirely(GridLine::parse, "calc(1 - 1)").is_err());
+}
+
+#[test]
+fn test_grid_line_negative() {
+    // `-1` (and other negative, non-span line numbers) means "the Nth line
+    // from the end" and is distinct from a negative `span`, which is
+    // rejected below; it must parse and round-trip as-is.
+    let line = parse_entirely(GridLine::parse, "-1").unwrap();
+    assert!(!line.is_span);
+    assert_eq!(line.to_css_string(), "-1");
+
+    // A `<line-start> / <line-end>` placement built from two such lines
+    // survives independently; each half only cares about its own line
+    // number.
+    let start = parse_entirely(GridLine::parse, "-1").unwrap();
+    let end = parse_entirely(GridLine::parse, "-3").unwrap();
+    assert_eq!(start.to_css_string(), "-1");
+    assert_eq!(end.to_css_string(), "-3");
+
+    // Negative spans remain invalid.
+    assert!(parse_entirely(GridLine::parse, "span -1").is_err());
+}
+
+#[test]
+fn test_grid_line_constructors() {
+    use style::values::CustomIdent;
+
+    let span = GridLine::span(Integer::new(5));
+    assert!(span.is_span);
+    assert_eq!(span.to_css_string(), "span 5");
+
+    let line = GridLine::from_line(Integer::new(5));
+    assert!(!line.is_span);
+    assert_eq!(line.to_css_string(), "5");
+
+    let named = GridLine::named(CustomIdent(::style::Atom::from("foo")));
+    assert!(named.is_ident_only());
+    assert_eq!(named.to_css_string(), "foo");
+
+    // Out-of-range values are clamped, matching the parser's own clamping.
+    let clamped = GridLine::from_line(Integer::new(20000));
+    assert_eq!(clamped.to_css_string(), "10000");
+}
+
+#[test]
+fn test_grid_line_clamped_line_num() {
+    let line = parse_entirely(GridLine::parse, "10001").unwrap();
+    assert_eq!(line.clamped_line_num(), 10000);
+}
+
+#[test]
+fn test_grid_line_normalized() {
+    // `from_line` already clamps internally, same as the parser, so
+    // `normalized()` is a no-op here; it only matters for a `GridLine` built
+    // via a struct literal that skipped that clamp.
+    let out_of_range = GridLine::from_line(Integer::new(10001));
+    let parsed = parse_entirely(GridLine::parse, "10001").unwrap();
+    assert_eq!(out_of_range.normalized(), parsed);
+}
+
+#[test]
+fn test_grid_line_resolve_against() {
+    use style::values::CustomIdent;
+
+    // `span 3` from a start of line 2 resolves to line 5.
+    let span = GridLine::span(Integer::new(3));
+    assert_eq!(span.resolve_against(2, 10), 5);
+
+    // A positive line number resolves to itself, regardless of start.
+    let positive = GridLine::from_line(Integer::new(4));
+    assert_eq!(positive.resolve_against(1, 10), 4);
+
+    // A negative line number counts back from the last explicit line:
+    // with 10 explicit lines, `-1` is line 10, `-2` is line 9.
+    let negative = GridLine::from_line(Integer::new(-1));
+    assert_eq!(negative.resolve_against(1, 10), 10);
+    let negative_two = GridLine::from_line(Integer::new(-2));
+    assert_eq!(negative_two.resolve_against(1, 10), 9);
+
+    // `auto` can't be resolved numerically.
+    assert_eq!(GridLine::auto().resolve_against(1, 10), GridLine::UNRESOLVED_LINE);
+
+    // Neither can a named line; that requires the grid's named-lines table.
+    let named = GridLine::named(CustomIdent(::style::Atom::from("foo")));
+    assert_eq!(named.resolve_against(1, 10), GridLine::UNRESOLVED_LINE);
+}
+
+#[test]
+fn test_grid_line_parse_lenient_clamps_span_zero() {
+    // The strict parser rejects `span 0` outright.
+    assert!(parse_entirely(GridLine::parse, "span 0").is_err());
+
+    // `parse_lenient` clamps it up to `span 1` instead.
+    assert_eq!(
+        parse_entirely(GridLine::parse_lenient, "span 0")
+            .unwrap()
+            .to_css_string(),
+        "span 1"
+    );
+
+    // A bare `0` (no `span`) stays invalid either way; only a zero *span*
+    // count is rehabilitated.
+    assert!(parse_entirely(GridLine::parse_lenient, "0").is_err());
+
+    // Non-zero spans and other values are unaffected.
+    assert_eq!(
+        parse_entirely(GridLine::parse_lenient, "span 3")
+            .unwrap()
+            .to_css_string(),
+        "span 3"
+    );
+}
+
+#[test]
+fn test_grid_line_span_serializations() {
+    // Locks down the three `span` serialization shapes: ident-only, number
+    // and ident together, and number-only.
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span foo")
+            .unwrap()
+            .to_css_string(),
+        "span foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span 2 foo")
+            .unwrap()
+            .to_css_string(),
+        "span 2 foo"
+    );
+    assert_eq!(
+        parse_entirely(GridLine::parse, "span 2")
+            .unwrap()
+            .to_css_string(),
+        "span 2"
+    );
+}
+
+#[test]
+fn test_track_list_rejects_second_auto_repeat() {
+    use style::values::specified::TrackList;
+
+    // At most one <auto-repeat> is allowed per <track-list>.
+    assert!(parse_entirely(TrackList::parse, "repeat(auto-fill, 1fr) repeat(auto-fit, 100px)")
+        .is_err());
+}
+
+#[test]
+fn test_track_list_rejects_nested_repeat() {
+    use style::values::specified::TrackList;
+
+    // `repeat()` cannot nest, whether the outer repeat is fixed or auto.
+    assert!(parse_entirely(TrackList::parse, "repeat(2, repeat(2, 1fr))").is_err());
+    assert!(parse_entirely(TrackList::parse, "repeat(auto-fill, repeat(2, 1fr))").is_err());
+}
+
+#[test]
+fn test_track_list_allows_auto_repeat_with_fixed_size() {
+    use style::values::specified::TrackList;
+
+    // An <auto-repeat> may be combined with <fixed-size> values elsewhere
+    // in the list; it just can't be combined with intrinsic/flexible ones.
+    assert!(parse_entirely(TrackList::parse, "repeat(auto-fill, 100px) 200px").is_ok());
+}
+
+#[test]
+fn test_track_list_auto_repeat_index() {
+    use style::values::specified::TrackList;
+
+    // `auto_repeat_index` records the position of the sole <auto-repeat>
+    // among `values`, even when it's sandwiched between fixed tracks.
+    let list = parse_entirely(TrackList::parse, "100px repeat(auto-fill, 1fr) 200px").unwrap();
+    assert_eq!(list.auto_repeat_index, 1);
+    assert!(list.has_auto_repeat());
+
+    // With no <auto-repeat> at all, the index is out of bounds.
+    let no_auto = parse_entirely(TrackList::parse, "100px 200px").unwrap();
+    assert!(!no_auto.has_auto_repeat());
+}
+
+#[test]
+fn test_track_list_no_stray_spaces_around_line_names() {
+    use style::values::specified::TrackList;
+
+    // No leading <line-names>: must not gain a leading space.
+    assert_eq!(
+        parse_entirely(TrackList::parse, "1fr 2fr")
+            .unwrap()
+            .to_css_string(),
+        "1fr 2fr"
+    );
+    // A leading, non-empty <line-names>: exactly one space before the track.
+    assert_eq!(
+        parse_entirely(TrackList::parse, "[a] 1fr")
+            .unwrap()
+            .to_css_string(),
+        "[a] 1fr"
+    );
+}
+
+#[test]
+fn test_track_repeat_count_rejects_zero_and_negative() {
+    use style::values::specified::TrackList;
+
+    // `repeat()`'s count is a <positive-integer>: zero and negative counts
+    // are a hard parse error, not silently clamped up to 1.
+    assert!(parse_entirely(TrackList::parse, "repeat(0, 1fr)").is_err());
+    assert!(parse_entirely(TrackList::parse, "repeat(-1, 1fr)").is_err());
+}
+
+#[test]
+fn test_track_repeat_count_clamps_to_max_grid_line() {
+    use style::values::specified::TrackList;
+
+    // Counts above MAX_GRID_LINE are clamped down to it, rather than
+    // rejected, matching how overlarge <grid-line> values are clamped.
+    assert_eq!(
+        parse_entirely(TrackList::parse, "repeat(10001, 1px)")
+            .unwrap()
+            .to_css_string(),
+        "repeat(10000, 1px)"
+    );
+    // A count already within range round-trips unchanged.
+    assert_eq!(
+        parse_entirely(TrackList::parse, "repeat(10000, 1px)")
+            .unwrap()
+            .to_css_string(),
+        "repeat(10000, 1px)"
+    );
+}
+
+#[test]
+fn test_track_repeat_count_accepts_calc() {
+    use style::values::specified::TrackList;
+
+    // calc() in the count position goes through the same parse_positive
+    // path as a literal integer, so it's clamped/rejected the same way; a
+    // non-clamped value keeps its `calc()` context on serialization, same
+    // as elsewhere in the grid types (see `test_grid_line_calc`).
+    assert_eq!(
+        parse_entirely(TrackList::parse, "repeat(calc(2 + 3), 1fr)")
+            .unwrap()
+            .to_css_string(),
+        "repeat(calc(5), 1fr)"
+    );
+    assert!(parse_entirely(TrackList::parse, "repeat(calc(1 - 1), 1fr)").is_err());
+}
+
+#[test]
+fn test_track_list_value_accessors() {
+    use style::values::specified::TrackList;
+
+    let list = parse_entirely(TrackList::parse, "100px repeat(2, 1fr)").unwrap();
+    assert_eq!(list.values.len(), 2);
+
+    let size = &list.values[0];
+    assert!(size.as_track_size().is_some());
+    assert!(size.as_repeat().is_none());
+
+    let repeat = &list.values[1];
+    assert!(repeat.as_track_size().is_none());
+    assert!(repeat.as_repeat().is_some());
+}
+
+#[test]
+fn test_implicit_grid_tracks_serialization() {
+    // Zero tracks (the initial value) serializes as `auto`.
+    assert_eq!(
+        ImplicitGridTracks::default().to_css_string(),
+        "auto"
+    );
+    // A single non-initial track.
+    assert_eq!(
+        parse_entirely(ImplicitGridTracks::parse, "100px")
+            .unwrap()
+            .to_css_string(),
+        "100px"
+    );
+    // Multiple tracks are space-separated, with no trailing space.
+    assert_eq!(
+        parse_entirely(ImplicitGridTracks::parse, "100px 200px")
+            .unwrap()
+            .to_css_string(),
+        "100px 200px"
+    );
+    assert_eq!(
+        parse_entirely(ImplicitGridTracks::parse, "100px 200px 1fr")
+            .unwrap()
+            .to_css_string(),
+        "100px 200px 1fr"
+    );
+}
+
+#[test]
+fn test_implicit_grid_tracks_len() {
+    let tracks = parse_entirely(ImplicitGridTracks::parse, "100px 200px 1fr").unwrap();
+    assert_eq!(tracks.len(), 3);
+    assert_eq!(ImplicitGridTracks::default().len(), 0);
+}
+
+#[test]
+fn test_fit_content_only_accepts_length_percentage() {
+    use style::values::specified::TrackSize;
+
+    assert!(parse_entirely(TrackSize::parse, "fit-content(200px)").is_ok());
+    assert!(parse_entirely(TrackSize::parse, "fit-content(20%)").is_ok());
+    assert!(parse_entirely(TrackSize::parse, "fit-content(1fr)").is_err());
+    assert!(parse_entirely(TrackSize::parse, "fit-content(auto)").is_err());
+    assert!(parse_entirely(TrackSize::parse, "fit-content(min-content)").is_err());
+    assert!(parse_entirely(TrackSize::parse, "fit-content(max-content)").is_err());
+}
+
+#[test]
+fn test_line_names_escape_round_trip() {
+    use style::values::specified::TrackList;
+
+    // A line name containing a space must be escaped on serialization, and
+    // re-parsing that serialization must produce the same name back.
+    let parsed = parse_entirely(TrackList::parse, r"[a\ b] 1fr").unwrap();
+    let serialized = parsed.to_css_string();
+    let reparsed = parse_entirely(TrackList::parse, &serialized).unwrap();
+    assert_eq!(parsed, reparsed);
+
+    // A line name starting with a digit only exists via a CSS escape, since
+    // an unescaped leading digit isn't a valid <custom-ident>; it must
+    // still round-trip losslessly.
+    let parsed = parse_entirely(TrackList::parse, r"[\31 23abc] 1fr").unwrap();
+    let serialized = parsed.to_css_string();
+    let reparsed = parse_entirely(TrackList::parse, &serialized).unwrap();
+    assert_eq!(parsed, reparsed);
+}
+
+// A small, fixed-seed xorshift generator, so that a failing round-trip is
+// reproducible without needing to depend on an external `proptest`-style
+// crate just for this one test.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // xorshift64*
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Returns a value in `[0, bound)`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+}
+
+fn gen_ident(rng: &mut Rng) -> String {
+    // Keep these short and alphabetic, so no escaping is ever required; the
+    // escaping case itself is already covered by
+    // `test_line_names_escape_round_trip`.
+    const NAMES: &[&str] = &["a", "b", "foo", "bar", "col-start", "row"];
+    NAMES[rng.below(NAMES.len())].to_string()
+}
+
+// Generates a `<line-names>` production, e.g. `[a b]` or `[]`.
+fn gen_line_names(rng: &mut Rng) -> String {
+    let count = rng.below(3);
+    let names: Vec<String> = (0..count).map(|_| gen_ident(rng)).collect();
+    format!("[{}]", names.join(" "))
+}
+
+// Generates a `<fixed-breadth>`, i.e. a length or percentage.
+fn gen_fixed_breadth(rng: &mut Rng) -> String {
+    if rng.one_in(2) {
+        format!("{}px", 1 + rng.below(500))
+    } else {
+        format!("{}%", 1 + rng.below(100))
+    }
+}
+
+// Generates a `<track-breadth>`. `fixed_only` restricts the output to
+// `<fixed-breadth>`, which is required inside `repeat(auto-fill, ...)` and
+// `repeat(auto-fit, ...)`, and alongside them elsewhere in the same
+// `<track-list>`.
+fn gen_track_breadth(rng: &mut Rng, fixed_only: bool) -> String {
+    if fixed_only {
+        return gen_fixed_breadth(rng);
+    }
+    match rng.below(5) {
+        0 => "auto".to_string(),
+        1 => "min-content".to_string(),
+        2 => "max-content".to_string(),
+        3 => format!("{}fr", 1 + rng.below(10)),
+        _ => gen_fixed_breadth(rng),
+    }
+}
+
+// Generates a `<track-size>`. When `fixed_only` is set (i.e. this size sits
+// alongside an `<auto-repeat>` in the same `<track-list>`), only
+// `<fixed-breadth>` is produced: `minmax()` and `fit-content()` are not
+// `is_fixed()`, and mixing them in would make the generated list invalid.
+fn gen_track_size(rng: &mut Rng, fixed_only: bool) -> String {
+    if fixed_only {
+        return gen_fixed_breadth(rng);
+    }
+    match rng.below(3) {
+        0 => gen_track_breadth(rng, false),
+        1 => format!(
+            "minmax({}, {})",
+            gen_track_breadth(rng, false),
+            gen_track_breadth(rng, false)
+        ),
+        _ => format!("fit-content({})", gen_fixed_breadth(rng)),
+    }
+}
+
+// Generates a `<track-list>`, any of whose tracks may be a fixed-count
+// `repeat()`, optionally including a single `repeat(auto-fill, ...)`.
+fn gen_track_list(rng: &mut Rng) -> String {
+    let track_count = 1 + rng.below(4);
+    let auto_repeat_index = if rng.one_in(3) {
+        Some(rng.below(track_count + 1))
+    } else {
+        None
+    };
+
+    let mut parts = Vec::new();
+    for i in 0..=track_count {
+        if rng.one_in(3) {
+            parts.push(gen_line_names(rng));
+        }
+        if i == track_count {
+            break;
+        }
+        if auto_repeat_index == Some(i) {
+            let inner_count = 1 + rng.below(3);
+            let mut inner = Vec::new();
+            for _ in 0..inner_count {
+                if rng.one_in(2) {
+                    inner.push(gen_line_names(rng));
+                }
+                inner.push(gen_fixed_breadth(rng));
+            }
+            parts.push(format!("repeat(auto-fill, {})", inner.join(" ")));
+        } else if rng.one_in(4) {
+            let count = 1 + rng.below(4);
+            parts.push(format!(
+                "repeat({}, {})",
+                count,
+                gen_track_size(rng, auto_repeat_index.is_some())
+            ));
+        } else {
+            parts.push(gen_track_size(rng, auto_repeat_index.is_some()));
+        }
+    }
+    parts.join(" ")
+}
+
+#[test]
+fn test_track_list_serialization_round_trips() {
+    use style::values::specified::TrackList;
+
+    let mut rng = Rng::new(0x5eed_1234_c0ff_ee01);
+    for _ in 0..200 {
+        let input = gen_track_list(&mut rng);
+        let parsed = match parse_entirely(TrackList::parse, &input) {
+            Ok(parsed) => parsed,
+            // Some generated combinations are grammatically invalid (e.g.
+            // `minmax()` with a flex minimum); skip those,
+            // we only care about round-tripping values that parsed.
+            Err(_) => continue,
+        };
+        let serialized = parsed.to_css_string();
+        let reparsed = parse_entirely(TrackList::parse, &serialized).unwrap_or_else(|_| {
+            panic!(
+                "serialization {:?} of {:?} failed to re-parse",
+                serialized, input
+            )
+        });
+        assert_eq!(
+            parsed, reparsed,
+            "input {:?} serialized to {:?}, which re-parsed to a different value",
+            input, serialized
+        );
+    }
+}
+
+#[test]
+fn test_track_size_minmax_rejects_flex_minimum() {
+    use style::values::specified::TrackSize;
+
+    // `<inflexible-breadth>` in the first `minmax()` position may not be a
+    // flex `<fr>`; only the second (maximum) position may be.
+    assert!(parse_entirely(TrackSize::parse, "minmax(1fr, 100px)").is_err());
+    assert!(parse_entirely(TrackSize::parse, "minmax(100px, 1fr)").is_ok());
+}
+
+#[test]
+fn test_track_list_rejects_line_names_only_repeat() {
+    use style::values::specified::{LineNameList, TrackList};
+
+    // `repeat(2, [a])` has no `<track-size>` at all, so it's the
+    // `<name-repeat>` production, not `<track-repeat>`: valid inside a
+    // subgrid's `<line-name-list>`, but not a plain `<track-list>`.
+    assert!(parse_entirely(TrackList::parse, "repeat(2, [a])").is_err());
+    assert!(parse_entirely(TrackList::parse, "repeat(2, [a] [b])").is_err());
+    assert!(
+        parse_entirely(LineNameList::parse, "subgrid repeat(2, [a])").is_ok()
+    );
+}
+
+#[test]
+fn test_line_name_list_recompute_expanded_length() {
+    use style::values::specified::LineNameList;
+
+    // `[a] [b] repeat(3, [c] [d])`: 2 plain <line-names> plus a fixed
+    // repeat(3, ...) of a two-line-names group, i.e. 2 + 3 * 2 = 8.
+    let mut list = parse_entirely(LineNameList::parse, "subgrid [a] [b] repeat(3, [c] [d])")
+        .unwrap();
+    assert_eq!(list.expanded_line_names_length, 8);
+
+    // Corrupt the precomputed length, then recompute it and check it's
+    // brought back in sync.
+    list.expanded_line_names_length = 0;
+    list.recompute_expanded_length();
+    assert_eq!(list.expanded_line_names_length, 8);
+    list.validate();
+}
+
+#[test]
+fn test_line_name_list_serializes_empty_and_non_empty_line_names() {
+    use style::values::specified::LineNameList;
+
+    // `NameRepeat::to_css` has to special-case an empty `<line-names>`
+    // member (writing " []" itself, since `concat_serialize_idents` skips
+    // empty slices entirely — see its doc comment), while a plain, non-repeat
+    // `<line-names>` entry always writes its brackets regardless of emptiness
+    // via `LineNameListValue::LineNames`'s own `to_css`. Pin down that mixing
+    // an empty repeat member with an empty and a non-empty plain entry all
+    // round-trip identically, with consistent single-space separation
+    // throughout.
+    for input in &[
+        "subgrid repeat(2, [] [a]) [b]",
+        "subgrid [] repeat(2, [a] []) []",
+        "subgrid repeat(auto-fill, [])",
+    ] {
+        let parsed = parse_entirely(LineNameList::parse, input)
+            .unwrap_or_else(|_| panic!("{:?} failed to parse", input));
+        assert_eq!(&parsed.to_css_string(), input);
+    }
+}
+
+// Generates a subgrid `<line-name-list>`, i.e. `subgrid <line-names>*`, with
+// at most one `repeat(auto-fill, <line-names>+)`.
+fn gen_line_name_list(rng: &mut Rng) -> String {
+    let mut parts = vec!["subgrid".to_string()];
+    let entry_count = 1 + rng.below(4);
+    let auto_fill_index = if rng.one_in(3) {
+        Some(rng.below(entry_count))
+    } else {
+        None
+    };
+    for i in 0..entry_count {
+        if Some(i) == auto_fill_index {
+            let inner_count = 1 + rng.below(3);
+            let inner: Vec<String> = (0..inner_count).map(|_| gen_line_names(rng)).collect();
+            parts.push(format!("repeat(auto-fill, {})", inner.join(" ")));
+        } else {
+            parts.push(gen_line_names(rng));
+        }
+    }
+    parts.join(" ")
+}
+
+#[test]
+fn test_line_name_list_serialization_round_trips() {
+    use style::values::specified::LineNameList;
+
+    let mut rng = Rng::new(0x5eed_1234_c0ff_ee02);
+    for _ in 0..200 {
+        let input = gen_line_name_list(&mut rng);
+        let parsed = match parse_entirely(LineNameList::parse, &input) {
+            Ok(parsed) => parsed,
+            Err(_) => continue,
+        };
+        let serialized = parsed.to_css_string();
+        let reparsed = parse_entirely(LineNameList::parse, &serialized).unwrap_or_else(|_| {
+            panic!(
+                "serialization {:?} of {:?} failed to re-parse",
+                serialized, input
+            )
+        });
+        assert_eq!(
+            parsed, reparsed,
+            "input {:?} serialized to {:?}, which re-parsed to a different value",
+            input, serialized
+        );
+    }
+}
@@ -115,6 +115,7 @@ mod border;
 mod box_;
 mod column;
 mod effects;
+mod grid;
 mod image;
 mod inherited_text;
 mod outline;
@@ -69,6 +69,22 @@ impl DiplomatWriteable {
     pub fn flush(&mut self) {
         (self.flush)(self);
     }
+
+    /// Ensure the buffer has room for at least `capacity` bytes, growing it
+    /// up front if needed.
+    ///
+    /// Callers that can estimate the eventual output size (e.g. a
+    /// worst-case expansion factor for a transformation like normalization)
+    /// can use this to avoid repeated incremental `grow` calls over the
+    /// course of a single `write_str`. Returns `false` if the requested
+    /// capacity could not be allocated, in which case the writeable is left
+    /// unchanged and can still be used normally.
+    pub fn reserve(&mut self, capacity: usize) -> bool {
+        if capacity <= self.cap {
+            return true;
+        }
+        (self.grow)(self, capacity)
+    }
 }
 impl fmt::Write for DiplomatWriteable {
     fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
@@ -392,6 +392,11 @@ impl From<TimeZoneError> for ICU4XError {
     }
 }
 
+/// `NormalizerError::Data` keeps its own `Data*Error` discriminant (via
+/// `DataError`'s `From` impl below) so a data provider missing the
+/// normalization key is distinguishable from `NormalizerValidationError`,
+/// which is reserved for ill-formed input (see e.g.
+/// `normalizer::ffi::ICU4XComposingNormalizer::normalize_strict`).
 #[cfg(feature = "icu_normalizer")]
 impl From<NormalizerError> for ICU4XError {
     fn from(e: NormalizerError) -> Self {
@@ -2,16 +2,279 @@
 // called LICENSE at the top level of the ICU4X source tree
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
+/// Lossily re-encodes `bytes` as a well-formed UTF-8 `String`, replacing each
+/// maximal ill-formed subsequence with a single REPLACEMENT CHARACTER, and
+/// returns how many substitutions were made alongside the result.
+///
+/// This mirrors the algorithm behind `String::from_utf8_lossy`, but also
+/// reports the substitution count, which the standard library does not
+/// expose.
+fn lossy_utf8_with_count(bytes: &[u8]) -> (alloc::string::String, usize) {
+    let mut valid = alloc::string::String::with_capacity(bytes.len());
+    let mut replacements = 0;
+    let mut rest = bytes;
+
+    loop {
+        match core::str::from_utf8(rest) {
+            Ok(chunk) => {
+                valid.push_str(chunk);
+                break;
+            },
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                // Safe because `from_utf8` just validated this prefix.
+                valid.push_str(unsafe { core::str::from_utf8_unchecked(&rest[..valid_up_to]) });
+                valid.push('\u{FFFD}');
+                replacements += 1;
+
+                let invalid_len = e.error_len().unwrap_or(rest.len() - valid_up_to).max(1);
+                rest = &rest[valid_up_to + invalid_len..];
+            },
+        }
+    }
+
+    (valid, replacements)
+}
+
+/// Returns whether `c` has the Unicode `Bidi_Control` property, i.e. it is
+/// one of the small, fixed set of formatting characters (RLO, LRO, RLE, LRE,
+/// PDF, RLI, LRI, FSI, PDI, ALM, LRM, RLM) that can override or embed the
+/// bidirectional algorithm's directionality.
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' // ALM
+            | '\u{200E}' // LRM
+            | '\u{200F}' // RLM
+            | '\u{202A}' // LRE
+            | '\u{202B}' // RLE
+            | '\u{202C}' // PDF
+            | '\u{202D}' // LRO
+            | '\u{202E}' // RLO
+            | '\u{2066}' // LRI
+            | '\u{2067}' // RLI
+            | '\u{2068}' // FSI
+            | '\u{2069}' // PDI
+    )
+}
+
+/// Returns whether `c` falls in one of the Unicode blocks reserved for
+/// combining marks (the combining diacritical marks blocks and their
+/// extensions, plus the combining half marks).
+///
+/// This is a conservative approximation of "has canonical combining class
+/// > 0": it is used only to decide where it's safe to split a normalized
+/// chunk, and under-splitting (holding over a starter that didn't need it)
+/// is harmless, whereas failing to hold over an actual combining mark would
+/// produce incorrect output, so callers should treat the byte immediately
+/// before a run of these as the last safe split point.
+fn is_likely_combining_mark(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036F // Combining Diacritical Marks
+            | 0x1AB0..=0x1AFF // Combining Diacritical Marks Extended
+            | 0x1DC0..=0x1DFF // Combining Diacritical Marks Supplement
+            | 0x20D0..=0x20FF // Combining Diacritical Marks for Symbols
+            | 0xFE20..=0xFE2F // Combining Half Marks
+    )
+}
+
+/// Returns the byte index at which `s` must be split for chunked
+/// normalization: everything before the index is safe to finalize now,
+/// everything from it onward must be held over because it could still
+/// combine with whatever arrives at the start of the next chunk.
+///
+/// This holds back the trailing run of `is_likely_combining_mark` code
+/// points, same as before, but now also the one base character right
+/// before that run (or the last character outright, if there is no
+/// trailing run): a base character with no combining marks *yet* can still
+/// be the target of one that shows up in the next chunk, so it isn't safe
+/// to finalize either. See `normalize_chunk` for a worked example of why
+/// holding back only the trailing marks isn't enough.
+fn safe_finalize_boundary(s: &str) -> usize {
+    let mut chars = s.char_indices().rev().peekable();
+    let mut boundary = s.len();
+    while let Some(&(index, c)) = chars.peek() {
+        if !is_likely_combining_mark(c) {
+            break;
+        }
+        boundary = index;
+        chars.next();
+    }
+    if let Some(&(index, _)) = chars.peek() {
+        boundary = index;
+    }
+    boundary
+}
+
+/// Forward-search counterpart of `safe_finalize_boundary`, used by
+/// `ICU4XComposingNormalizer::next_safe_boundary`: returns the smallest
+/// byte index `>= after` at which `s` can safely be split.
+///
+/// Walking forward past a trailing run of combining marks (as before) is
+/// enough when the returned boundary lands strictly before `s.len()`,
+/// since everything after it is already known text that won't change.
+/// But landing exactly on `s.len()` is a different case: nothing further
+/// is known, so a trailing base character there could still combine with
+/// a mark that arrives right after `s` ends. In that case this backs off
+/// to before that trailing base character too, as far as `after` allows.
+fn safe_split_boundary(s: &str, after: usize) -> usize {
+    let mut boundary = after.min(s.len());
+    while boundary < s.len() && !s.is_char_boundary(boundary) {
+        boundary += 1;
+    }
+
+    while boundary < s.len() {
+        let c = s[boundary..].chars().next().unwrap();
+        if !is_likely_combining_mark(c) {
+            break;
+        }
+        boundary += c.len_utf8();
+    }
+
+    if boundary == s.len() {
+        if let Some((index, c)) = s[..boundary].char_indices().last() {
+            if !is_likely_combining_mark(c) {
+                boundary = index.max(after);
+            }
+        }
+    }
+
+    boundary
+}
+
+/// Returns whether `c` matches the XML 1.0 `Char` production
+/// (<https://www.w3.org/TR/xml/#NT-Char>):
+/// `#x9 | #xA | #xD | [#x20-#xD7FF] | [#xE000-#xFFFD] | [#x10000-#x10FFFF]`.
+fn is_xml_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x9 | 0xA
+            | 0xD
+            | 0x20..=0xD7FF
+            | 0xE000..=0xFFFD
+            | 0x10000..=0x10FFFF
+    )
+}
+
+/// A tiny spinlock, used below to guard the constructor cache.
+///
+/// `no_std` builds of this crate (e.g. wasm) have no access to
+/// `std::sync::Mutex`, and pulling in a dependency just to protect a handful
+/// of cached pointers would be overkill.
+struct SpinLock<T> {
+    locked: core::sync::atomic::AtomicBool,
+    value: core::cell::UnsafeCell<T>,
+}
+
+// Safety: access to `value` is only ever granted from within `with`, while
+// `locked` is held, so it is never aliased.
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    const fn new(value: T) -> Self {
+        Self {
+            locked: core::sync::atomic::AtomicBool::new(false),
+            value: core::cell::UnsafeCell::new(value),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        use core::sync::atomic::Ordering;
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let result = f(unsafe { &mut *self.value.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+/// Cache of normalizers already constructed from a given `ICU4XDataProvider`,
+/// keyed by `ICU4XDataProvider::cache_key`.
+///
+/// This cache lives for the lifetime of the process: entries are never
+/// evicted, so a long-running service creating normalizers from many
+/// short-lived, distinct providers should prefer the uncached constructors.
+/// The key is a monotonic id assigned once at provider construction, not the
+/// provider's address, so it is never reused: a provider being dropped and a
+/// later, unrelated provider happening to land at the same address can't
+/// alias each other's cache entry the way keying by address would allow.
+static NFC_CACHE: SpinLock<alloc::collections::BTreeMap<u64, alloc::sync::Arc<icu_normalizer::ComposingNormalizer>>> =
+    SpinLock::new(alloc::collections::BTreeMap::new());
+
+/// Returns whether `bytes` is entirely ASCII, i.e. contains no byte `>=
+/// 0x80`. Used as a fast path for normalization forms where ASCII text is
+/// always already normalized (see call sites for the caveats on which
+/// forms that actually holds for).
+fn is_ascii_only(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&b| b < 0x80)
+}
+
+/// Conservative worst-case output/input byte-length expansion factors,
+/// used to pre-size a `DiplomatWriteable` before normalizing. Composition
+/// never lengthens text by more than a small, form-independent margin;
+/// decomposition (worst case NFKD) can expand a single precomposed
+/// character into several combining marks.
+const COMPOSING_EXPANSION_FACTOR: usize = 1;
+const DECOMPOSING_EXPANSION_FACTOR: usize = 3;
+
+/// The normalizer selected at runtime by `ICU4XNormalizer::create`, storing
+/// whichever of the two underlying normalizer types the requested form maps
+/// to.
+enum NormalizerInner {
+    Composing(alloc::sync::Arc<icu_normalizer::ComposingNormalizer>),
+    Decomposing(icu_normalizer::DecomposingNormalizer),
+}
+
 #[diplomat::bridge]
 pub mod ffi {
+    use super::NFC_CACHE;
     use crate::{errors::ffi::ICU4XError, provider::ffi::ICU4XDataProvider};
     use alloc::boxed::Box;
+    use alloc::sync::Arc;
     use diplomat_runtime::DiplomatWriteable;
     use icu_normalizer::{ComposingNormalizer, DecomposingNormalizer};
 
+    /// The result of `ICU4XComposingNormalizer::normalize_chunk_streaming`:
+    /// how many bytes of the input chunk were consumed and normalized into
+    /// the output.
+    pub struct ICU4XNormalizeResult {
+        pub consumed: usize,
+    }
+
+    /// The result of `ICU4XComposingNormalizer::normalize_into_slice`: how
+    /// many bytes of `out` were written, and whether the normalized result
+    /// didn't fit and had to be truncated.
+    pub struct ICU4XNormalizeSliceResult {
+        pub written: usize,
+        pub truncated: bool,
+    }
+
+    /// Tri-state result of a normalization quick-check: definitely
+    /// normalized, definitely not, or "maybe" (a full `normalize` and
+    /// compare is required to be sure).
+    pub enum ICU4XNormalizationCheckResult {
+        Yes = 0,
+        No = 1,
+        Maybe = 2,
+    }
+
+    /// Which composing normalization form an `ICU4XComposingNormalizer` was
+    /// constructed for, as returned by `ICU4XComposingNormalizer::kind`.
+    pub enum ICU4XComposingNormalizerKind {
+        Nfc = 0,
+        Nfkc = 1,
+        NfkcCasefold = 2,
+    }
+
     #[diplomat::opaque]
     #[diplomat::rust_link(icu::normalizer::ComposingNormalizer, Struct)]
-    pub struct ICU4XComposingNormalizer(pub ComposingNormalizer);
+    pub struct ICU4XComposingNormalizer(pub Arc<ComposingNormalizer>, ICU4XComposingNormalizerKind);
 
     impl ICU4XComposingNormalizer {
         /// Construct a new ICU4XComposingNormalizer instance for NFC
@@ -19,15 +282,61 @@ pub mod ffi {
             icu::normalizer::ComposingNormalizer::try_new_nfc_unstable,
             FnInStruct
         )]
+        ///
+        /// `provider` may be backed by either an `AnyProvider` or a
+        /// `BufferProvider` (see `ICU4XDataProvider::create_fs` vs.
+        /// `create_from_byte_slice`, for instance): `ICU4XDataProviderInner`
+        /// dispatches to whichever was actually supplied internally, so
+        /// there is no separate `create_nfc_with_buffer_provider` — this
+        /// constructor already is that lower-level, raw-tables-friendly
+        /// entry point when `provider` wraps a `BufferProvider`.
+        ///
+        /// A `provider` with no normalization data at all fails with
+        /// `ICU4XError::DataMissingDataKeyError`; one whose data doesn't
+        /// deserialize to the struct shape this crate's `icu_normalizer`
+        /// expects (e.g. baked by an incompatible ICU4X version) fails with
+        /// `ICU4XError::DataMismatchedTypeError` instead — the `DataError`
+        /// this constructor propagates through `?` already keeps those
+        /// discriminants distinct (see the `NormalizerError`/`DataError`
+        /// conversions in `errors.rs`), so no separate guard is needed here.
         pub fn create_nfc(
             provider: &ICU4XDataProvider,
         ) -> Result<Box<ICU4XComposingNormalizer>, ICU4XError> {
             Ok(Box::new(ICU4XComposingNormalizer(
-                ComposingNormalizer::try_new_nfc_unstable(&provider.0)?,
+                Arc::new(ComposingNormalizer::try_new_nfc_unstable(&provider.0)?),
+                ICU4XComposingNormalizerKind::Nfc,
+            )))
+        }
+
+        /// Construct a new ICU4XComposingNormalizer instance for NFC, sharing
+        /// the underlying normalizer data (via `Arc`) with any other instance
+        /// already cached for this `provider`. See the module-level cache
+        /// documentation for the cache's lifetime and identity caveats.
+        ///
+        /// This is safe to call concurrently from multiple threads.
+        pub fn create_nfc_cached(
+            provider: &ICU4XDataProvider,
+        ) -> Result<Box<ICU4XComposingNormalizer>, ICU4XError> {
+            let key = provider.cache_key();
+            if let Some(cached) = NFC_CACHE.with(|cache| cache.get(&key).cloned()) {
+                return Ok(Box::new(ICU4XComposingNormalizer(
+                    cached,
+                    ICU4XComposingNormalizerKind::Nfc,
+                )));
+            }
+
+            let normalizer = Arc::new(ComposingNormalizer::try_new_nfc_unstable(&provider.0)?);
+            NFC_CACHE.with(|cache| cache.insert(key, normalizer.clone()));
+            Ok(Box::new(ICU4XComposingNormalizer(
+                normalizer,
+                ICU4XComposingNormalizerKind::Nfc,
             )))
         }
 
         /// Construct a new ICU4XComposingNormalizer instance for NFKC
+        ///
+        /// See `create_nfc` for how a missing data key is distinguished from
+        /// mismatched data.
         #[diplomat::rust_link(
             icu::normalizer::ComposingNormalizer::try_new_nfkc_unstable,
             FnInStruct
@@ -36,10 +345,76 @@ pub mod ffi {
             provider: &ICU4XDataProvider,
         ) -> Result<Box<ICU4XComposingNormalizer>, ICU4XError> {
             Ok(Box::new(ICU4XComposingNormalizer(
-                ComposingNormalizer::try_new_nfkc_unstable(&provider.0)?,
+                Arc::new(ComposingNormalizer::try_new_nfkc_unstable(&provider.0)?),
+                ICU4XComposingNormalizerKind::Nfkc,
+            )))
+        }
+
+        /// Construct a new ICU4XComposingNormalizer instance for NFC using
+        /// data baked into the binary at compile time, without a provider.
+        ///
+        /// The vendored `icu_normalizer` in this tree only exposes the
+        /// `_unstable` constructors above, which require an
+        /// `ICU4XDataProvider`; the no-provider, compiled-data constructors
+        /// (`ComposingNormalizer::new_nfc` behind a `compiled_data` Cargo
+        /// feature) were added in a later ICU4X release than the one vendored
+        /// here. This always returns `ICU4XError::UnknownError` rather than
+        /// silently falling back to a provider it wasn't given; callers that
+        /// need this should use `create_nfc` with an explicit provider until
+        /// the vendored `icu_normalizer` is updated.
+        ///
+        /// Gated on the `compiled_data` Cargo feature (disabled by default)
+        /// so these permanently-broken stubs compile away entirely rather
+        /// than shipping as symbols that always fail at runtime.
+        #[cfg(feature = "compiled_data")]
+        pub fn create_nfc_with_compiled_data() -> Result<Box<ICU4XComposingNormalizer>, ICU4XError>
+        {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Construct a new ICU4XComposingNormalizer instance for NFKC using
+        /// data baked into the binary at compile time, without a provider.
+        ///
+        /// See `create_nfc_with_compiled_data` for why this is currently
+        /// unimplemented in this vendored copy of `icu_normalizer`, and for
+        /// why it's gated on the `compiled_data` feature.
+        #[cfg(feature = "compiled_data")]
+        pub fn create_nfkc_with_compiled_data(
+        ) -> Result<Box<ICU4XComposingNormalizer>, ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Construct a new ICU4XComposingNormalizer instance for
+        /// NFKC_Casefold, ICU4X's case-folding compatibility normalizer used
+        /// for case-insensitive identifier matching and IDNA-like
+        /// processing.
+        #[diplomat::rust_link(
+            icu::normalizer::ComposingNormalizer::try_new_nfkc_casefold_unstable,
+            FnInStruct
+        )]
+        pub fn create_nfkc_casefold(
+            provider: &ICU4XDataProvider,
+        ) -> Result<Box<ICU4XComposingNormalizer>, ICU4XError> {
+            Ok(Box::new(ICU4XComposingNormalizer(
+                Arc::new(ComposingNormalizer::try_new_nfkc_casefold_unstable(
+                    &provider.0,
+                )?),
+                ICU4XComposingNormalizerKind::NfkcCasefold,
             )))
         }
 
+        /// Returns which composing normalization form this instance was
+        /// constructed for.
+        pub fn kind(&self) -> ICU4XComposingNormalizerKind {
+            match &self.1 {
+                ICU4XComposingNormalizerKind::Nfc => ICU4XComposingNormalizerKind::Nfc,
+                ICU4XComposingNormalizerKind::Nfkc => ICU4XComposingNormalizerKind::Nfkc,
+                ICU4XComposingNormalizerKind::NfkcCasefold => {
+                    ICU4XComposingNormalizerKind::NfkcCasefold
+                },
+            }
+        }
+
         /// Normalize a (potentially ill-formed) UTF8 string
         ///
         /// Errors are mapped to REPLACEMENT CHARACTER
@@ -56,14 +431,53 @@ pub mod ffi {
             hidden
         )]
         pub fn normalize(&self, s: &str, write: &mut DiplomatWriteable) -> Result<(), ICU4XError> {
+            write.reserve(s.len() * super::COMPOSING_EXPANSION_FACTOR);
             let s = s.as_bytes(); // #2520
             self.0.normalize_utf8_to(s, write)?;
             Ok(())
         }
 
+        /// Normalize a UTF8 string, rejecting it instead of substituting
+        /// REPLACEMENT CHARACTER if it is ill-formed.
+        ///
+        /// This is the strict counterpart to `normalize` above, for callers
+        /// (validators, linters) that want to know an input was ill-formed
+        /// rather than silently accept a lossy result.
+        pub fn normalize_strict(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<(), ICU4XError> {
+            // `NormalizerValidationError` mirrors `icu_normalizer::NormalizerError::ValidationError`,
+            // distinguishing ill-formed input from a data-load failure
+            // (`self.0.normalize_utf8_to` below funnels its own errors
+            // through the `From<NormalizerError>` impl, which already keeps
+            // data errors on their own `Data*Error` discriminants).
+            if core::str::from_utf8(s.as_bytes()).is_err() {
+                return Err(ICU4XError::NormalizerValidationError);
+            }
+            self.0.normalize_utf8_to(s.as_bytes(), write)?;
+            Ok(())
+        }
+
         /// Check if a (potentially ill-formed) UTF8 string is normalized
         ///
         /// Errors are mapped to REPLACEMENT CHARACTER
+        ///
+        /// Deliberately does not take an ASCII fast path the way
+        /// `ICU4XDecomposingNormalizer::is_normalized` does: this type can
+        /// be constructed as NFKC_Casefold (`create_nfkc_casefold`), under
+        /// which plain ASCII letters are not already normalized (uppercase
+        /// folds to lowercase), so "input is pure ASCII" doesn't imply
+        /// "input is already normalized" here.
+        ///
+        /// The "quick, scan-until-a-non-starter" style optimization for
+        /// predominantly-starter text (bailing out as soon as a run of
+        /// combining-class-0 code points is confirmed already composed)
+        /// lives inside `icu::normalizer::ComposingNormalizer` itself, not
+        /// in this FFI wrapper: this crate depends on `icu_normalizer` from
+        /// crates.io rather than vendoring its source, so there's no local
+        /// copy of that scan to specialize further here.
         #[diplomat::rust_link(icu::normalizer::ComposingNormalizer::is_normalized_utf8, FnInStruct)]
         #[diplomat::rust_link(
             icu::normalizer::ComposingNormalizer::is_normalized,
@@ -74,11 +488,461 @@ pub mod ffi {
             let s = s.as_bytes(); // #2520
             self.0.is_normalized_utf8(s)
         }
+
+        /// Like `is_normalized`, but only scans up to `max_bytes` of `s`,
+        /// for callers that only care about a bounded prefix (e.g. the
+        /// first 256 bytes of a large field) and want to avoid scanning
+        /// megabytes of input for a "good enough" answer.
+        ///
+        /// If `max_bytes` lands in the middle of a scalar value, it's
+        /// rounded down to the previous UTF-8 character boundary, so the
+        /// scanned prefix is always well-formed on its own.
+        pub fn is_normalized_prefix(&self, s: &str, max_bytes: usize) -> bool {
+            let mut boundary = max_bytes.min(s.len());
+            while boundary > 0 && !s.is_char_boundary(boundary) {
+                boundary -= 1;
+            }
+            self.0.is_normalized_utf8(s[..boundary].as_bytes())
+        }
+
+        /// Quick-check whether a (potentially ill-formed) UTF8 string is
+        /// normalized, distinguishing "definitely normalized", "definitely
+        /// not", and "maybe" (needs a full check) rather than collapsing to
+        /// a single boolean.
+        ///
+        /// The vendored `icu_normalizer` in this tree only exposes the full
+        /// check (`is_normalized_utf8`, used by `is_normalized` above) and
+        /// not the separate, cheaper quick-check property tables a real
+        /// UAX #15 quick-check algorithm consults; this method therefore
+        /// always resolves to `Yes` or `No` from that full check and never
+        /// currently returns `Maybe`. Callers should still treat `Maybe` as
+        /// a possible result and fall back to comparing against `normalize`
+        /// when they see it, so behavior is unaffected if this is later
+        /// backed by real quick-check data.
+        pub fn quick_check(&self, s: &str) -> ICU4XNormalizationCheckResult {
+            if self.0.is_normalized_utf8(s.as_bytes()) {
+                ICU4XNormalizationCheckResult::Yes
+            } else {
+                ICU4XNormalizationCheckResult::No
+            }
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, writing the
+        /// normalized form and returning whether the output differs from
+        /// the input, i.e. whether normalization was actually needed.
+        ///
+        /// This is cheaper than calling `is_normalized` followed by
+        /// `normalize`: it checks first and only performs the (potentially
+        /// allocating) normalization when the input isn't already
+        /// normalized. `false` means the input was passed through
+        /// unchanged.
+        pub fn normalize_if_needed(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<bool, ICU4XError> {
+            let bytes = s.as_bytes(); // #2520
+            if self.0.is_normalized_utf8(bytes) {
+                write.write_str(s);
+                return Ok(false);
+            }
+            write.reserve(s.len() * super::COMPOSING_EXPANSION_FACTOR);
+            self.0.normalize_utf8_to(bytes, write)?;
+            Ok(true)
+        }
+
+        /// Check if a single scalar value is normalized, without requiring
+        /// the caller to wrap it in a `&str` first.
+        pub fn is_normalized_char(&self, c: char) -> bool {
+            let mut buf = [0u8; 4];
+            self.0.is_normalized_utf8(c.encode_utf8(&mut buf).as_bytes())
+        }
+
+        /// Normalize a single scalar value, without requiring the caller to
+        /// wrap it in a `&str` first. A single input character can still
+        /// normalize into several output characters (e.g. a precomposed
+        /// character under NFD), so `write` still takes the usual
+        /// `DiplomatWriteable`.
+        pub fn normalize_char(&self, c: char, write: &mut DiplomatWriteable) -> Result<(), ICU4XError> {
+            let mut buf = [0u8; 4];
+            self.0
+                .normalize_utf8_to(c.encode_utf8(&mut buf).as_bytes(), write)?;
+            Ok(())
+        }
+
+        /// Normalize a sequence of scalar values passed as raw `u32` code
+        /// points, without requiring the caller to assemble a `&str` first.
+        /// Each `u32` that isn't a valid Unicode scalar value (a surrogate,
+        /// or simply out of range) is mapped to U+FFFD REPLACEMENT
+        /// CHARACTER, matching how ill-formed UTF-8 is handled elsewhere in
+        /// this file.
+        pub fn normalize_code_points(
+            &self,
+            input: &[u32],
+            write: &mut DiplomatWriteable,
+        ) -> Result<(), ICU4XError> {
+            let s: alloc::string::String = input
+                .iter()
+                .map(|&cp| char::from_u32(cp).unwrap_or('\u{FFFD}'))
+                .collect();
+            self.0.normalize_utf8_to(s.as_bytes(), write)?; // #2520
+            Ok(())
+        }
+
+        /// Normalize each of `inputs` and write them to `write`, joined by
+        /// `separator`, in a single call. This amortizes FFI call overhead
+        /// for callers normalizing many short strings at once (e.g. a list
+        /// of identifiers) instead of calling `normalize` once per string.
+        pub fn normalize_batch(
+            &self,
+            inputs: &[&str],
+            write: &mut DiplomatWriteable,
+            separator: char,
+        ) -> Result<(), ICU4XError> {
+            let mut sep_buf = [0u8; 4];
+            let separator = separator.encode_utf8(&mut sep_buf);
+            for (i, s) in inputs.iter().enumerate() {
+                if i != 0 {
+                    write.write_str(separator);
+                }
+                self.0.normalize_utf8_to(s.as_bytes(), write)?; // #2520
+            }
+            Ok(())
+        }
+
+        /// Returns whether every element of `inputs` is already normalized.
+        /// Companion to `normalize_batch` for callers that only need a
+        /// yes/no answer for a whole batch.
+        pub fn is_normalized_batch(&self, inputs: &[&str]) -> bool {
+            inputs
+                .iter()
+                .all(|s| self.0.is_normalized_utf8(s.as_bytes()))
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, returning the
+        /// number of ill-formed sequences that were replaced with
+        /// REPLACEMENT CHARACTER, for callers that want to measure input
+        /// quality in addition to the normalized text. This is the
+        /// "normalize and count substitutions" entry point; there's no
+        /// separate `normalize_counting` — this already is that method.
+        pub fn normalize_count_replacements(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<usize, ICU4XError> {
+            let (valid, replacements) = crate::normalizer::lossy_utf8_with_count(s.as_bytes()); // #2520
+            self.0.normalize_to(&valid, write)?;
+            Ok(replacements)
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, allocating and
+        /// returning the normalized result rather than writing into a
+        /// caller-supplied `DiplomatWriteable`, for FFI targets that find an
+        /// owned return value easier to bind than the writeable pattern.
+        ///
+        /// Errors are mapped to REPLACEMENT CHARACTER, matching `normalize`.
+        pub fn normalize_to_string(&self, s: &str) -> Result<Box<str>, ICU4XError> {
+            let mut out = alloc::string::String::with_capacity(
+                s.len() * super::COMPOSING_EXPANSION_FACTOR,
+            );
+            self.0.normalize_utf8_to(s.as_bytes(), &mut out)?; // #2520
+            Ok(out.into_boxed_str())
+        }
+
+        /// UTF-16 counterpart of `normalize_to_string`.
+        ///
+        /// Blocked on the same missing UTF-16 writing support as
+        /// `normalize_utf16`: this vendored `diplomat-runtime` has no
+        /// UTF-16 equivalent of `DiplomatWriteable` to build the result
+        /// from, so this always returns `ICU4XError::UnknownError`.
+        pub fn normalize_utf16_to_vec(&self, _s: &[u16]) -> Result<Box<[u16]>, ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, returning
+        /// whether the normalized output contains any bidi control
+        /// character (RLO, LRO, and similar formatting characters).
+        ///
+        /// This supports spoofing defenses that want to reject identifiers
+        /// or display strings that use bidi controls to disguise their
+        /// rendered direction.
+        pub fn normalize_and_has_bidi_controls(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<bool, ICU4XError> {
+            let s = s.as_bytes(); // #2520
+            let mut normalized = alloc::string::String::new();
+            self.0.normalize_utf8_to(s, &mut normalized)?;
+            let has_bidi_controls = normalized.chars().any(super::is_bidi_control);
+            write.write_str(&normalized);
+            Ok(has_bidi_controls)
+        }
+
+        /// Normalize a chunk of a larger string that is being streamed in
+        /// pieces, writing the part of the normalized output that is safe to
+        /// finalize now to `write`, and any trailing sequence that could
+        /// still combine with the next chunk's leading characters to
+        /// `held_over`.
+        ///
+        /// The caller must prepend the contents of `held_over` to the next
+        /// chunk before calling `normalize_chunk` (or `normalize`, for the
+        /// final chunk) again. This is the low-level building block for
+        /// chunked normalization; see the streaming normalizer for a
+        /// higher-level API.
+        pub fn normalize_chunk(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+            held_over: &mut DiplomatWriteable,
+        ) -> Result<(), ICU4XError> {
+            let s = s.as_bytes(); // #2520
+            let mut normalized = alloc::string::String::new();
+            self.0.normalize_utf8_to(s, &mut normalized)?;
+
+            let split_at = super::safe_finalize_boundary(&normalized);
+
+            write.write_str(&normalized[..split_at]);
+            held_over.write_str(&normalized[split_at..]);
+            Ok(())
+        }
+
+        /// Normalize as much of a chunk of a larger, incoming string as can
+        /// be safely finalized right now, without requiring the caller to
+        /// track a separate held-over buffer: writes the normalized prefix
+        /// to `write` and reports how many input bytes were consumed.
+        ///
+        /// This differs from `normalize_chunk` above by holding back
+        /// *input* that might still combine with the next chunk (instead of
+        /// consuming the whole input and holding back part of the
+        /// *output*): the caller should prepend `s[result.consumed..]` to
+        /// the next chunk before calling this again, and use `normalize`
+        /// (not this method) on the final chunk. Both approaches land on the
+        /// same normalized text; this one suits callers that would rather
+        /// track one leftover slice of the input than an extra output
+        /// buffer.
+        pub fn normalize_chunk_streaming(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<ICU4XNormalizeResult, ICU4XError> {
+            let consumed = super::safe_finalize_boundary(s);
+
+            let mut normalized = alloc::string::String::new();
+            self.0
+                .normalize_utf8_to(s.as_bytes()[..consumed].as_ref(), &mut normalized)?; // #2520
+            write.write_str(&normalized);
+            Ok(ICU4XNormalizeResult { consumed })
+        }
+
+        /// Returns the smallest byte index `>= after` (rounded up to a
+        /// UTF-8 character boundary) at which `s` can safely be split for
+        /// independent chunked normalization: splitting there and
+        /// normalizing each side separately, then concatenating, gives the
+        /// same result as normalizing `s` as a whole.
+        ///
+        /// This walks forward from `after` past any run of characters
+        /// `is_likely_combining_mark` flags, the same heuristic
+        /// `normalize_chunk`/`normalize_chunk_streaming` use to decide what
+        /// to hold back — splitting in the middle of such a run risks
+        /// separating a base character from combining marks that would
+        /// otherwise compose with it. If that walk reaches the end of `s`,
+        /// the trailing base character is backed off too (as far as `after`
+        /// allows), since a combining mark arriving right after `s` ends
+        /// could still compose with it. Returns `s.len()` if no earlier safe
+        /// boundary exists at or after `after`.
+        pub fn next_safe_boundary(&self, s: &str, after: usize) -> usize {
+            super::safe_split_boundary(s, after)
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, returning
+        /// whether the normalized output consists entirely of characters
+        /// valid in the XML 1.0 `Char` production. This is a common gate
+        /// before serializing normalized text into XML, which disallows
+        /// most control characters.
+        pub fn normalize_xml_safe(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<bool, ICU4XError> {
+            let s = s.as_bytes(); // #2520
+            let mut normalized = alloc::string::String::new();
+            self.0.normalize_utf8_to(s, &mut normalized)?;
+            let is_xml_safe = normalized.chars().all(super::is_xml_char);
+            write.write_str(&normalized);
+            Ok(is_xml_safe)
+        }
+
+        /// Returns the byte length of the longest prefix of `s` that is
+        /// already normalized, so callers can skip re-normalizing text they
+        /// know hasn't changed. Returns `s.len()` if `s` is fully
+        /// normalized.
+        ///
+        /// Stops at the first ill-formed byte, if any, treating everything
+        /// from that point on as not-yet-known to be normalized (matching
+        /// how the lossy methods above only look at well-formed input).
+        ///
+        /// This vendored `icu_normalizer` has no native "normalized up to"
+        /// primitive to call into, so this is built by normalizing the
+        /// (well-formed prefix of the) input and walking the two byte
+        /// strings together to find where they first diverge, rounded down
+        /// to the nearest UTF-8 character boundary.
+        pub fn is_normalized_up_to(&self, s: &str) -> usize {
+            let bytes = s.as_bytes();
+            let valid_len = match core::str::from_utf8(bytes) {
+                Ok(_) => bytes.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            let valid = &s[..valid_len];
+
+            let mut normalized = alloc::string::String::new();
+            if self.0.normalize_utf8_to(valid.as_bytes(), &mut normalized).is_err() {
+                return 0;
+            }
+
+            let mut common = valid
+                .as_bytes()
+                .iter()
+                .zip(normalized.as_bytes().iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            while common > 0 && !valid.is_char_boundary(common) {
+                common -= 1;
+            }
+            common
+        }
+
+        /// Compute the UTS 39 confusable "skeleton" of `s` for confusable
+        /// detection: two strings with equal skeletons are confusable.
+        ///
+        /// This build of ICU4X does not vendor the confusables data table
+        /// (there is no `icu_properties` confusables map available to this
+        /// crate), so this always returns `ICU4XError::UnknownError`
+        /// rather than silently producing an incorrect skeleton. Once a
+        /// confusables data provider is available, this should apply NFKC
+        /// followed by the confusables mapping, per the algorithm in
+        /// <https://www.unicode.org/reports/tr39/#Confusable_Detection>.
+        pub fn skeleton(
+            &self,
+            _s: &str,
+            _write: &mut DiplomatWriteable,
+        ) -> Result<(), ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Check if a (potentially ill-formed) UTF-16 string is normalized.
+        ///
+        /// Ill-formed code unit sequences (unpaired surrogates) are mapped to
+        /// REPLACEMENT CHARACTER, the same way the UTF-8 methods above map
+        /// ill-formed byte sequences, before checking normalization.
+        pub fn is_normalized_utf16(&self, s: &[u16]) -> bool {
+            let s: alloc::string::String = char::decode_utf16(s.iter().copied())
+                .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            self.0.is_normalized_utf8(s.as_bytes())
+        }
+
+        /// Normalize a (potentially ill-formed) UTF-16 string.
+        ///
+        /// This vendored copy of `diplomat_runtime` has no UTF-16 output
+        /// writeable (there is no `DiplomatWriteable16`, only the UTF-8
+        /// `DiplomatWriteable` used elsewhere in this file), so there is
+        /// nowhere for the normalized UTF-16 result to be written; adding
+        /// that would mean extending `diplomat_runtime` itself, which is out
+        /// of scope here. This always returns `ICU4XError::UnknownError`
+        /// rather than silently writing UTF-8 through a UTF-16 signature.
+        /// See `is_normalized_utf16` above for the read-only UTF-16 check,
+        /// which does not need an output writeable.
+        pub fn normalize_utf16(&self, _s: &[u16]) -> Result<(), ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string into the
+        /// caller-provided `out` buffer, without allocating, returning the
+        /// number of bytes written.
+        ///
+        /// Returns `ICU4XError::OutOfBoundsError` if `out` is too small to
+        /// hold the normalized result. `ICU4XError` is a fieldless enum
+        /// shared by every method in this crate, so it has no room to carry
+        /// the needed length back to the caller; callers that need to size
+        /// a retry buffer should over-allocate (normalization never grows
+        /// UTF-8 input by more than a small constant factor) rather than
+        /// relying on an exact needed-length report.
+        pub fn normalize_into_fixed(&self, s: &str, out: &mut [u8]) -> Result<usize, ICU4XError> {
+            let s = s.as_bytes(); // #2520
+            let mut normalized = alloc::string::String::new();
+            self.0.normalize_utf8_to(s, &mut normalized)?;
+
+            let bytes = normalized.as_bytes();
+            if bytes.len() > out.len() {
+                return Err(ICU4XError::OutOfBoundsError);
+            }
+
+            out[..bytes.len()].copy_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string into the
+        /// caller-provided `out` buffer, writing as much of the normalized
+        /// result as fits instead of failing outright when it doesn't.
+        ///
+        /// If `out` is too small, the written prefix is rounded down to the
+        /// nearest UTF-8 character boundary (so `out[..written]` is always
+        /// valid UTF-8 on its own, never a partial scalar) and
+        /// `truncated` is set. Unlike `normalize_into_fixed`, an
+        /// undersized buffer is not an error here; prefer that method
+        /// instead when a truncated result is useless to the caller.
+        pub fn normalize_into_slice(
+            &self,
+            s: &str,
+            out: &mut [u8],
+        ) -> Result<ICU4XNormalizeSliceResult, ICU4XError> {
+            let s = s.as_bytes(); // #2520
+            let mut normalized = alloc::string::String::new();
+            self.0.normalize_utf8_to(s, &mut normalized)?;
+
+            let bytes = normalized.as_bytes();
+            if bytes.len() <= out.len() {
+                out[..bytes.len()].copy_from_slice(bytes);
+                return Ok(ICU4XNormalizeSliceResult {
+                    written: bytes.len(),
+                    truncated: false,
+                });
+            }
+
+            let mut written = out.len();
+            while written > 0 && !normalized.is_char_boundary(written) {
+                written -= 1;
+            }
+            out[..written].copy_from_slice(&bytes[..written]);
+            Ok(ICU4XNormalizeSliceResult {
+                written,
+                truncated: true,
+            })
+        }
+
+        /// Clones this normalizer handle. The underlying loaded data is
+        /// `Arc`-backed, so this is cheap and the clone shares it with the
+        /// original rather than reloading or duplicating it; useful for FFI
+        /// hosts that want to hand a normalizer to another thread or
+        /// closure without keeping the original alive through some other
+        /// means.
+        pub fn clone(&self) -> Box<ICU4XComposingNormalizer> {
+            Box::new(ICU4XComposingNormalizer(self.0.clone(), self.kind()))
+        }
+    }
+
+    /// Which decomposing normalization form an `ICU4XDecomposingNormalizer`
+    /// was constructed for, as returned by
+    /// `ICU4XDecomposingNormalizer::kind`.
+    pub enum ICU4XDecompositionKind {
+        Nfd = 0,
+        Nfkd = 1,
     }
 
     #[diplomat::opaque]
     #[diplomat::rust_link(icu::normalizer::DecomposingNormalizer, Struct)]
-    pub struct ICU4XDecomposingNormalizer(pub DecomposingNormalizer);
+    pub struct ICU4XDecomposingNormalizer(pub DecomposingNormalizer, ICU4XDecompositionKind);
 
     impl ICU4XDecomposingNormalizer {
         /// Construct a new ICU4XDecomposingNormalizer instance for NFC
@@ -86,15 +950,25 @@ pub mod ffi {
             icu::normalizer::DecomposingNormalizer::try_new_nfd_unstable,
             FnInStruct
         )]
+        ///
+        /// See `ICU4XComposingNormalizer::create_nfc` for why there is no
+        /// separate `create_nfd_with_buffer_provider`: `provider` already
+        /// dispatches to a `BufferProvider` transparently when built from
+        /// one, and for how a missing data key is distinguished from
+        /// mismatched data.
         pub fn create_nfd(
             provider: &ICU4XDataProvider,
         ) -> Result<Box<ICU4XDecomposingNormalizer>, ICU4XError> {
             Ok(Box::new(ICU4XDecomposingNormalizer(
                 DecomposingNormalizer::try_new_nfd_unstable(&provider.0)?,
+                ICU4XDecompositionKind::Nfd,
             )))
         }
 
         /// Construct a new ICU4XDecomposingNormalizer instance for NFKC
+        ///
+        /// See `ICU4XComposingNormalizer::create_nfc` for how a missing data
+        /// key is distinguished from mismatched data.
         #[diplomat::rust_link(
             icu::normalizer::DecomposingNormalizer::try_new_nfkd_unstable,
             FnInStruct
@@ -104,9 +978,46 @@ pub mod ffi {
         ) -> Result<Box<ICU4XDecomposingNormalizer>, ICU4XError> {
             Ok(Box::new(ICU4XDecomposingNormalizer(
                 DecomposingNormalizer::try_new_nfkd_unstable(&provider.0)?,
+                ICU4XDecompositionKind::Nfkd,
             )))
         }
 
+        /// Returns which decomposing normalization form this instance was
+        /// constructed for.
+        pub fn kind(&self) -> ICU4XDecompositionKind {
+            match &self.1 {
+                ICU4XDecompositionKind::Nfd => ICU4XDecompositionKind::Nfd,
+                ICU4XDecompositionKind::Nfkd => ICU4XDecompositionKind::Nfkd,
+            }
+        }
+
+        /// Construct a new ICU4XDecomposingNormalizer instance for NFD using
+        /// data baked into the binary at compile time, without a provider.
+        ///
+        /// See `ICU4XComposingNormalizer::create_nfc_with_compiled_data` for
+        /// why this is currently unimplemented in this vendored copy of
+        /// `icu_normalizer`, and for why it's gated on the `compiled_data`
+        /// feature.
+        #[cfg(feature = "compiled_data")]
+        pub fn create_nfd_with_compiled_data(
+        ) -> Result<Box<ICU4XDecomposingNormalizer>, ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Construct a new ICU4XDecomposingNormalizer instance for NFKD
+        /// using data baked into the binary at compile time, without a
+        /// provider.
+        ///
+        /// See `ICU4XComposingNormalizer::create_nfc_with_compiled_data` for
+        /// why this is currently unimplemented in this vendored copy of
+        /// `icu_normalizer`, and for why it's gated on the `compiled_data`
+        /// feature.
+        #[cfg(feature = "compiled_data")]
+        pub fn create_nfkd_with_compiled_data(
+        ) -> Result<Box<ICU4XDecomposingNormalizer>, ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
         /// Normalize a (potentially ill-formed) UTF8 string
         ///
         /// Errors are mapped to REPLACEMENT CHARACTER
@@ -127,11 +1038,34 @@ pub mod ffi {
             hidden
         )]
         pub fn normalize(&self, s: &str, write: &mut DiplomatWriteable) -> Result<(), ICU4XError> {
+            write.reserve(s.len() * super::DECOMPOSING_EXPANSION_FACTOR);
             let s = s.as_bytes(); // #2520
             self.0.normalize_utf8_to(s, write)?;
             Ok(())
         }
 
+        /// Normalize a UTF8 string, rejecting it instead of substituting
+        /// REPLACEMENT CHARACTER if it is ill-formed.
+        ///
+        /// See `ICU4XComposingNormalizer::normalize_strict` for why callers
+        /// might prefer this over the lossy `normalize` above.
+        pub fn normalize_strict(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<(), ICU4XError> {
+            // `NormalizerValidationError` mirrors `icu_normalizer::NormalizerError::ValidationError`,
+            // distinguishing ill-formed input from a data-load failure
+            // (`self.0.normalize_utf8_to` below funnels its own errors
+            // through the `From<NormalizerError>` impl, which already keeps
+            // data errors on their own `Data*Error` discriminants).
+            if core::str::from_utf8(s.as_bytes()).is_err() {
+                return Err(ICU4XError::NormalizerValidationError);
+            }
+            self.0.normalize_utf8_to(s.as_bytes(), write)?;
+            Ok(())
+        }
+
         /// Check if a (potentially ill-formed) UTF8 string is normalized
         ///
         /// Errors are mapped to REPLACEMENT CHARACTER
@@ -146,7 +1080,448 @@ pub mod ffi {
         )]
         pub fn is_normalized(&self, s: &str) -> bool {
             let s = s.as_bytes(); // #2520
+            // NFD/NFKD never decompose or reorder plain ASCII (no ASCII
+            // code point has a canonical decomposition or nonzero
+            // combining class), so pure-ASCII input is always already
+            // normalized under both forms this type can represent.
+            if super::is_ascii_only(s) {
+                return true;
+            }
             self.0.is_normalized_utf8(s)
         }
+
+        /// See `ICU4XComposingNormalizer::is_normalized_prefix`.
+        pub fn is_normalized_prefix(&self, s: &str, max_bytes: usize) -> bool {
+            let mut boundary = max_bytes.min(s.len());
+            while boundary > 0 && !s.is_char_boundary(boundary) {
+                boundary -= 1;
+            }
+            let prefix = &s[..boundary];
+            if super::is_ascii_only(prefix.as_bytes()) {
+                return true;
+            }
+            self.0.is_normalized_utf8(prefix.as_bytes())
+        }
+
+        /// Quick-check whether a (potentially ill-formed) UTF8 string is
+        /// normalized. See `ICU4XComposingNormalizer::quick_check` for why
+        /// this never currently returns `Maybe`.
+        pub fn quick_check(&self, s: &str) -> ICU4XNormalizationCheckResult {
+            let bytes = s.as_bytes();
+            if super::is_ascii_only(bytes) || self.0.is_normalized_utf8(bytes) {
+                ICU4XNormalizationCheckResult::Yes
+            } else {
+                ICU4XNormalizationCheckResult::No
+            }
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, writing the
+        /// normalized form and returning whether the output differs from
+        /// the input.
+        ///
+        /// See `ICU4XComposingNormalizer::normalize_if_needed` for the
+        /// rationale; `false` means the input was passed through unchanged.
+        pub fn normalize_if_needed(
+            &self,
+            s: &str,
+            write: &mut DiplomatWriteable,
+        ) -> Result<bool, ICU4XError> {
+            let bytes = s.as_bytes(); // #2520
+            if self.0.is_normalized_utf8(bytes) {
+                write.write_str(s);
+                return Ok(false);
+            }
+            write.reserve(s.len() * super::DECOMPOSING_EXPANSION_FACTOR);
+            self.0.normalize_utf8_to(bytes, write)?;
+            Ok(true)
+        }
+
+        /// Check if a single scalar value is normalized.
+        ///
+        /// See `ICU4XComposingNormalizer::is_normalized_char`.
+        pub fn is_normalized_char(&self, c: char) -> bool {
+            let mut buf = [0u8; 4];
+            self.0.is_normalized_utf8(c.encode_utf8(&mut buf).as_bytes())
+        }
+
+        /// Normalize a single scalar value. A single input character can
+        /// still normalize into several output characters (e.g. a
+        /// precomposed character under NFD), so `write` still takes the
+        /// usual `DiplomatWriteable`.
+        pub fn normalize_char(&self, c: char, write: &mut DiplomatWriteable) -> Result<(), ICU4XError> {
+            let mut buf = [0u8; 4];
+            self.0
+                .normalize_utf8_to(c.encode_utf8(&mut buf).as_bytes(), write)?;
+            Ok(())
+        }
+
+        /// Normalize each of `inputs` and write them to `write`, joined by
+        /// `separator`, in a single call.
+        ///
+        /// See `ICU4XComposingNormalizer::normalize_batch`.
+        pub fn normalize_batch(
+            &self,
+            inputs: &[&str],
+            write: &mut DiplomatWriteable,
+            separator: char,
+        ) -> Result<(), ICU4XError> {
+            let mut sep_buf = [0u8; 4];
+            let separator = separator.encode_utf8(&mut sep_buf);
+            for (i, s) in inputs.iter().enumerate() {
+                if i != 0 {
+                    write.write_str(separator);
+                }
+                self.0.normalize_utf8_to(s.as_bytes(), write)?; // #2520
+            }
+            Ok(())
+        }
+
+        /// Returns whether every element of `inputs` is already normalized.
+        pub fn is_normalized_batch(&self, inputs: &[&str]) -> bool {
+            inputs
+                .iter()
+                .all(|s| self.0.is_normalized_utf8(s.as_bytes()))
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, allocating and
+        /// returning the normalized result.
+        ///
+        /// See `ICU4XComposingNormalizer::normalize_to_string`.
+        pub fn normalize_to_string(&self, s: &str) -> Result<Box<str>, ICU4XError> {
+            let mut out = alloc::string::String::with_capacity(
+                s.len() * super::DECOMPOSING_EXPANSION_FACTOR,
+            );
+            self.0.normalize_utf8_to(s.as_bytes(), &mut out)?; // #2520
+            Ok(out.into_boxed_str())
+        }
+
+        /// UTF-16 counterpart of `normalize_to_string`.
+        ///
+        /// See `ICU4XComposingNormalizer::normalize_utf16_to_vec` for why
+        /// this always returns `ICU4XError::UnknownError`.
+        pub fn normalize_utf16_to_vec(&self, _s: &[u16]) -> Result<Box<[u16]>, ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Check if a (potentially ill-formed) UTF-16 string is normalized.
+        ///
+        /// See `ICU4XComposingNormalizer::is_normalized_utf16` for the
+        /// ill-formed input handling this mirrors.
+        pub fn is_normalized_utf16(&self, s: &[u16]) -> bool {
+            let s: alloc::string::String = char::decode_utf16(s.iter().copied())
+                .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            self.0.is_normalized_utf8(s.as_bytes())
+        }
+
+        /// Normalize a (potentially ill-formed) UTF-16 string.
+        ///
+        /// See `ICU4XComposingNormalizer::normalize_utf16` for why this is
+        /// currently unimplemented in this vendored copy of
+        /// `diplomat_runtime`.
+        pub fn normalize_utf16(&self, _s: &[u16]) -> Result<(), ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Returns the byte length of the longest prefix of `s` that is
+        /// already normalized.
+        ///
+        /// See `ICU4XComposingNormalizer::is_normalized_up_to` for how this
+        /// is computed and how ill-formed input and character boundaries are
+        /// handled.
+        pub fn is_normalized_up_to(&self, s: &str) -> usize {
+            let bytes = s.as_bytes();
+            let valid_len = match core::str::from_utf8(bytes) {
+                Ok(_) => bytes.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            let valid = &s[..valid_len];
+
+            let mut normalized = alloc::string::String::new();
+            if self.0.normalize_utf8_to(valid.as_bytes(), &mut normalized).is_err() {
+                return 0;
+            }
+
+            let mut common = valid
+                .as_bytes()
+                .iter()
+                .zip(normalized.as_bytes().iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            while common > 0 && !valid.is_char_boundary(common) {
+                common -= 1;
+            }
+            common
+        }
+
+        /// Clones this normalizer handle, cheaply: the loaded data
+        /// underlying `DecomposingNormalizer` is reference-counted
+        /// internally, so this shares it with the original rather than
+        /// reloading or duplicating it. See
+        /// `ICU4XComposingNormalizer::clone` for the analogous method on
+        /// the composing side.
+        pub fn clone(&self) -> Box<ICU4XDecomposingNormalizer> {
+            Box::new(ICU4XDecomposingNormalizer(self.0.clone(), self.kind()))
+        }
+    }
+
+    /// The four concrete normalization forms, for selecting a form at
+    /// runtime with `ICU4XNormalizer::create` instead of picking between the
+    /// `ICU4XComposingNormalizer`/`ICU4XDecomposingNormalizer` constructors
+    /// statically.
+    pub enum ICU4XNormalizerForm {
+        Nfc = 0,
+        Nfd = 1,
+        Nfkc = 2,
+        Nfkd = 3,
+    }
+
+    /// A normalizer whose form (NFC, NFD, NFKC, or NFKD) is selected at
+    /// runtime, for bindings that pick the form dynamically (e.g. from a
+    /// config string) and would otherwise have to carry their own dispatch
+    /// between the four static constructors above.
+    #[diplomat::opaque]
+    pub struct ICU4XNormalizer(super::NormalizerInner);
+
+    impl ICU4XNormalizer {
+        /// Returns a conservative worst-case output/input byte-length
+        /// expansion factor for `form`, so embedders can pre-size their own
+        /// buffers the same way `normalize` pre-sizes its `DiplomatWriteable`
+        /// via `DiplomatWriteable::reserve`.
+        pub fn expansion_factor(form: ICU4XNormalizerForm) -> usize {
+            match form {
+                ICU4XNormalizerForm::Nfc | ICU4XNormalizerForm::Nfkc => {
+                    super::COMPOSING_EXPANSION_FACTOR
+                },
+                ICU4XNormalizerForm::Nfd | ICU4XNormalizerForm::Nfkd => {
+                    super::DECOMPOSING_EXPANSION_FACTOR
+                },
+            }
+        }
+
+        /// Construct a new ICU4XNormalizer for the given form.
+        pub fn create(
+            provider: &ICU4XDataProvider,
+            form: ICU4XNormalizerForm,
+        ) -> Result<Box<ICU4XNormalizer>, ICU4XError> {
+            use super::NormalizerInner;
+            Ok(Box::new(ICU4XNormalizer(match form {
+                ICU4XNormalizerForm::Nfc => NormalizerInner::Composing(Arc::new(
+                    ComposingNormalizer::try_new_nfc_unstable(&provider.0)?,
+                )),
+                ICU4XNormalizerForm::Nfkc => NormalizerInner::Composing(Arc::new(
+                    ComposingNormalizer::try_new_nfkc_unstable(&provider.0)?,
+                )),
+                ICU4XNormalizerForm::Nfd => {
+                    NormalizerInner::Decomposing(DecomposingNormalizer::try_new_nfd_unstable(&provider.0)?)
+                },
+                ICU4XNormalizerForm::Nfkd => {
+                    NormalizerInner::Decomposing(DecomposingNormalizer::try_new_nfkd_unstable(&provider.0)?)
+                },
+            })))
+        }
+
+        /// Normalize a (potentially ill-formed) UTF8 string, dispatching to
+        /// whichever form was selected at construction.
+        ///
+        /// Errors are mapped to REPLACEMENT CHARACTER
+        pub fn normalize(&self, s: &str, write: &mut DiplomatWriteable) -> Result<(), ICU4XError> {
+            let factor = match &self.0 {
+                super::NormalizerInner::Composing(_) => super::COMPOSING_EXPANSION_FACTOR,
+                super::NormalizerInner::Decomposing(_) => super::DECOMPOSING_EXPANSION_FACTOR,
+            };
+            write.reserve(s.len() * factor);
+            let s = s.as_bytes(); // #2520
+            match &self.0 {
+                super::NormalizerInner::Composing(n) => n.normalize_utf8_to(s, write)?,
+                super::NormalizerInner::Decomposing(n) => n.normalize_utf8_to(s, write)?,
+            }
+            Ok(())
+        }
+
+        /// Check if a (potentially ill-formed) UTF8 string is normalized in
+        /// whichever form was selected at construction.
+        ///
+        /// Errors are mapped to REPLACEMENT CHARACTER
+        ///
+        /// Takes an ASCII fast path: unlike raw `ICU4XComposingNormalizer`,
+        /// this type can only be built for the four plain forms
+        /// (`ICU4XNormalizerForm` has no NFKC_Casefold variant), and none of
+        /// NFC/NFD/NFKC/NFKD change plain ASCII text, so pure-ASCII input is
+        /// always already normalized here.
+        pub fn is_normalized(&self, s: &str) -> bool {
+            let s = s.as_bytes(); // #2520
+            if super::is_ascii_only(s) {
+                return true;
+            }
+            match &self.0 {
+                super::NormalizerInner::Composing(n) => n.is_normalized_utf8(s),
+                super::NormalizerInner::Decomposing(n) => n.is_normalized_utf8(s),
+            }
+        }
+
+        /// Detect which of the four normalization forms `s` already
+        /// satisfies, without the caller having to construct four
+        /// `ICU4XNormalizer`s and call `is_normalized` on each themselves.
+        ///
+        /// This constructs (and drops) a normalizer per form internally;
+        /// callers checking the same provider repeatedly should prefer
+        /// keeping their own long-lived `ICU4XNormalizer`s and calling
+        /// `is_normalized` directly, since this does not share the
+        /// `ICU4XComposingNormalizer::create_nfc_cached`-style cache.
+        pub fn detect_forms(
+            provider: &ICU4XDataProvider,
+            s: &str,
+        ) -> Result<ICU4XNormalizationForms, ICU4XError> {
+            Ok(ICU4XNormalizationForms {
+                nfc: ICU4XNormalizer::create(provider, ICU4XNormalizerForm::Nfc)?.is_normalized(s),
+                nfd: ICU4XNormalizer::create(provider, ICU4XNormalizerForm::Nfd)?.is_normalized(s),
+                nfkc: ICU4XNormalizer::create(provider, ICU4XNormalizerForm::Nfkc)?
+                    .is_normalized(s),
+                nfkd: ICU4XNormalizer::create(provider, ICU4XNormalizerForm::Nfkd)?
+                    .is_normalized(s),
+            })
+        }
+    }
+
+    /// Which of the four normalization forms a string satisfies, as
+    /// returned by `ICU4XNormalizer::detect_forms`.
+    pub struct ICU4XNormalizationForms {
+        pub nfc: bool,
+        pub nfd: bool,
+        pub nfkc: bool,
+        pub nfkd: bool,
+    }
+
+    /// UTS #46 mapping, used by domain-name (IDNA) processing.
+    ///
+    /// This vendored `icu_normalizer` predates UTS #46 support (it landed in
+    /// a later ICU4X release); this crate is also not wired up to the
+    /// separate, unrelated `idna` crate (a `rust-url` component vendored
+    /// elsewhere in this tree for the `url` crate's own use), since pulling
+    /// data from a differently-sourced IDNA implementation into an
+    /// ICU4X-data-driven FFI surface would be a layering mismatch, not a
+    /// drop-in replacement. `map` always returns `ICU4XError::UnknownError`
+    /// until this vendored `icu_normalizer` gains its own UTS #46 module.
+    #[diplomat::opaque]
+    pub struct ICU4XUts46Mapper;
+
+    impl ICU4XUts46Mapper {
+        /// Construct a new ICU4XUts46Mapper instance.
+        pub fn create(
+            _provider: &ICU4XDataProvider,
+        ) -> Result<Box<ICU4XUts46Mapper>, ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+
+        /// Apply UTS #46 mapping to `s`.
+        pub fn map(&self, _s: &str, _write: &mut DiplomatWriteable) -> Result<(), ICU4XError> {
+            Err(ICU4XError::UnknownError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_bidi_control, is_xml_char, lossy_utf8_with_count, safe_finalize_boundary,
+        safe_split_boundary,
+    };
+
+    #[test]
+    fn is_bidi_control_recognizes_all_named_controls() {
+        for c in [
+            '\u{061C}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}',
+            '\u{202E}', '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+        ] {
+            assert!(is_bidi_control(c), "{:?} should be a bidi control", c);
+        }
+    }
+
+    #[test]
+    fn is_bidi_control_rejects_ordinary_text() {
+        for c in ['a', ' ', '\u{2010}' /* hyphen, adjacent block */] {
+            assert!(!is_bidi_control(c), "{:?} should not be a bidi control", c);
+        }
+    }
+
+    #[test]
+    fn safe_finalize_boundary_holds_back_bare_trailing_base_character() {
+        // "e" with no combining mark (yet) must still be held back: a
+        // combining mark arriving in the next chunk (e.g. U+0301) could
+        // still compose with it into "é".
+        assert_eq!(safe_finalize_boundary("e"), 0);
+        assert_eq!(safe_finalize_boundary("xe"), 1);
+    }
+
+    #[test]
+    fn safe_finalize_boundary_holds_back_base_plus_combining_mark_run() {
+        // The base character preceding an already-present combining mark
+        // run must be held back along with the run, since a further
+        // combining mark could still extend it.
+        assert_eq!(safe_finalize_boundary("e\u{0301}"), 0);
+        assert_eq!(safe_finalize_boundary("xe\u{0301}"), 1);
+    }
+
+    #[test]
+    fn safe_finalize_boundary_finalizes_up_to_a_safe_run_of_plain_text() {
+        assert_eq!(safe_finalize_boundary(""), 0);
+    }
+
+    #[test]
+    fn safe_split_boundary_backs_off_past_trailing_base_character() {
+        // Splitting right after "e" would risk separating it from a
+        // combining mark on the other side of the split.
+        assert_eq!(safe_split_boundary("e", 0), 0);
+        assert_eq!(safe_split_boundary("xe", 0), 1);
+    }
+
+    #[test]
+    fn safe_split_boundary_respects_the_after_floor() {
+        // There's nowhere left to back off to without violating the
+        // `boundary >= after` contract, so this is a no-op.
+        assert_eq!(safe_split_boundary("e", 1), 1);
+    }
+
+    #[test]
+    fn safe_split_boundary_walks_past_an_existing_combining_run_mid_string() {
+        // "e\u{0301}x": asking for a split right after "e" (in the middle
+        // of "e" and its combining mark) walks forward past the whole
+        // mark run and lands right before "x", which is safe because "x"
+        // can't retroactively combine with what precedes it — no back-off
+        // past the string's end applies here.
+        let s = "e\u{0301}x";
+        assert_eq!(safe_split_boundary(s, "e".len()), "e\u{0301}".len());
+    }
+
+    #[test]
+    fn is_xml_char_accepts_the_xml_1_0_char_production() {
+        for c in ['\u{9}', '\u{A}', '\u{D}', ' ', 'a', '\u{D7FF}', '\u{E000}', '\u{10FFFF}'] {
+            assert!(is_xml_char(c), "{:?} should be a valid XML 1.0 Char", c);
+        }
+    }
+
+    #[test]
+    fn is_xml_char_rejects_disallowed_control_characters() {
+        for c in ['\u{0}', '\u{1}', '\u{B}', '\u{C}', '\u{1F}', '\u{FFFE}'] {
+            assert!(!is_xml_char(c), "{:?} should not be a valid XML 1.0 Char", c);
+        }
+    }
+
+    #[test]
+    fn lossy_utf8_with_count_reports_zero_for_well_formed_input() {
+        let (valid, replacements) = lossy_utf8_with_count("hello".as_bytes());
+        assert_eq!(valid, "hello");
+        assert_eq!(replacements, 0);
+    }
+
+    #[test]
+    fn lossy_utf8_with_count_counts_two_distinct_ill_formed_sequences() {
+        // b"a" + a lone continuation byte (ill-formed #1) + b"b" + another
+        // lone continuation byte (ill-formed #2) + b"c".
+        let bytes = [b'a', 0x80, b'b', 0x81, b'c'];
+        let (valid, replacements) = lossy_utf8_with_count(&bytes);
+        assert_eq!(valid, "a\u{FFFD}b\u{FFFD}c");
+        assert_eq!(replacements, 2);
     }
 }
@@ -6,11 +6,15 @@
 pub mod ffi {
     use crate::{errors::ffi::ICU4XError, provider::ffi::ICU4XDataProvider};
     use alloc::boxed::Box;
+    use diplomat_runtime::DiplomatWriteable;
     use icu_normalizer::properties::{
         CanonicalCombiningClassMap, CanonicalComposition, CanonicalDecomposition, Decomposed,
     };
 
     /// Lookup of the Canonical_Combining_Class Unicode property
+    ///
+    /// `get`/`get32` return 0 (Not_Reordered) for unassigned code points,
+    /// matching the underlying ICU4X map.
     #[diplomat::opaque]
     #[diplomat::rust_link(icu::normalizer::properties::CanonicalCombiningClassMap, Struct)]
     pub struct ICU4XCanonicalCombiningClassMap(pub CanonicalCombiningClassMap);
@@ -53,11 +57,44 @@ pub mod ffi {
         pub fn get32(&self, ch: u32) -> u8 {
             self.0.get32(ch).0
         }
+
+        /// Applies the Unicode Canonical Ordering Algorithm to `s`: stably
+        /// sorts each maximal run of consecutive non-starter code points (a
+        /// nonzero combining class, per `get`/`get32`) by their combining
+        /// class, leaving starters (combining class 0) fixed as the
+        /// boundaries between runs. This is the reordering step of
+        /// normalization on its own, for callers that already have
+        /// decomposed text and only need it canonically ordered rather than
+        /// a full `ICU4XDecomposingNormalizer` pass.
+        pub fn reorder(&self, s: &str, write: &mut DiplomatWriteable) {
+            let mut chars: alloc::vec::Vec<char> = s.chars().collect();
+
+            let mut i = 0;
+            while i < chars.len() {
+                if self.0.get(chars[i]).0 == 0 {
+                    i += 1;
+                    continue;
+                }
+                let start = i;
+                while i < chars.len() && self.0.get(chars[i]).0 != 0 {
+                    i += 1;
+                }
+                chars[start..i].sort_by_key(|&c| self.0.get(c).0);
+            }
+
+            let reordered: alloc::string::String = chars.into_iter().collect();
+            write.write_str(&reordered);
+        }
     }
 
     /// The raw canonical composition operation.
     ///
     /// Callers should generally use ICU4XComposingNormalizer unless they specifically need raw composition operations
+    ///
+    /// This lives alongside `ICU4XCanonicalCombiningClassMap` and
+    /// `ICU4XCanonicalDecomposition` in this module rather than a separate
+    /// `composition` module, since all three wrap sibling types from
+    /// `icu_normalizer::properties` and are constructed the same way.
     #[diplomat::opaque]
     #[diplomat::rust_link(icu::normalizer::properties::CanonicalComposition, Struct)]
     pub struct ICU4XCanonicalComposition(pub CanonicalComposition);
@@ -90,6 +127,11 @@ pub mod ffi {
     /// The outcome of non-recursive canonical decomposition of a character.
     /// `second` will be NUL when the decomposition expands to a single character
     /// (which may or may not be the original one)
+    ///
+    /// This is a fixed two-`char` struct, since Diplomat can't return a
+    /// variable-length result: it covers all three of ICU4X's `Decomposed`
+    /// outcomes (default/no decomposition and singleton both leave `second`
+    /// as NUL; only a two-character expansion sets both fields).
     #[diplomat::rust_link(icu::normalizer::properties::Decomposed, Enum)]
     pub struct ICU4XDecomposed {
         first: char,
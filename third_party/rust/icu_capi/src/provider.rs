@@ -22,6 +22,19 @@ pub enum ICU4XDataProviderInner {
     Buffer(Box<dyn BufferProvider + 'static>),
 }
 
+/// Returns a fresh identity for a newly constructed `ICU4XDataProvider`,
+/// unique for the lifetime of the process.
+///
+/// Callers that need to cache work keyed on "which provider instance is
+/// this" (e.g. `ICU4XComposingNormalizer::create_nfc_cached`) should use
+/// this instead of the provider's address: addresses can be reused once a
+/// provider is dropped, silently aliasing an unrelated later provider,
+/// whereas this counter never repeats a value.
+fn next_provider_id() -> u64 {
+    static NEXT_ID: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, core::sync::atomic::Ordering::Relaxed)
+}
+
 #[diplomat::bridge]
 pub mod ffi {
     use super::ICU4XDataProviderInner;
@@ -36,21 +49,38 @@ pub mod ffi {
     #[diplomat::opaque]
     /// An ICU4X data provider, capable of loading ICU4X data keys from some source.
     #[diplomat::rust_link(icu_provider, Mod)]
-    pub struct ICU4XDataProvider(pub ICU4XDataProviderInner);
+    pub struct ICU4XDataProvider(pub ICU4XDataProviderInner, u64);
 
     #[cfg(feature = "any_provider")]
     fn convert_any_provider<D: icu_provider::AnyProvider + 'static>(x: D) -> ICU4XDataProvider {
-        ICU4XDataProvider(super::ICU4XDataProviderInner::Any(Box::new(x)))
+        ICU4XDataProvider(
+            super::ICU4XDataProviderInner::Any(Box::new(x)),
+            super::next_provider_id(),
+        )
     }
 
     #[cfg(feature = "buffer_provider")]
     fn convert_buffer_provider<D: icu_provider::BufferProvider + 'static>(
         x: D,
     ) -> ICU4XDataProvider {
-        ICU4XDataProvider(super::ICU4XDataProviderInner::Buffer(Box::new(x)))
+        ICU4XDataProvider(
+            super::ICU4XDataProviderInner::Buffer(Box::new(x)),
+            super::next_provider_id(),
+        )
     }
 
     impl ICU4XDataProvider {
+        /// Returns a stable identity for this provider instance, suitable for
+        /// use as a cache key by callers like
+        /// `ICU4XComposingNormalizer::create_nfc_cached`.
+        ///
+        /// Unlike the provider's address, this is assigned once, when the
+        /// provider is constructed, and never reused, so it can't alias a
+        /// later, unrelated provider the way an address can once this one is
+        /// dropped and its memory is reclaimed.
+        pub(crate) fn cache_key(&self) -> u64 {
+            self.1
+        }
         /// Constructs an `FsDataProvider` and returns it as an [`ICU4XDataProvider`].
         /// Requires the `provider_fs` Cargo feature.
         /// Not supported in WASM.
@@ -104,7 +134,10 @@ pub mod ffi {
             hidden
         )]
         pub fn create_empty() -> Box<ICU4XDataProvider> {
-            Box::new(ICU4XDataProvider(ICU4XDataProviderInner::Empty))
+            Box::new(ICU4XDataProvider(
+                ICU4XDataProviderInner::Empty,
+                super::next_provider_id(),
+            ))
         }
 
         /// Creates a provider that tries the current provider and then, if the current provider
@@ -124,6 +157,10 @@ pub mod ffi {
         pub fn fork_by_key(&mut self, other: &mut ICU4XDataProvider) -> Result<(), ICU4XError> {
             #[allow(unused_imports)]
             use ICU4XDataProviderInner::*;
+            // Preserved across the reassignment below so this handle's cache
+            // identity (see `cache_key`) doesn't change just because its
+            // backing implementation did.
+            let id = self.1;
             *self = match (
                 core::mem::replace(&mut self.0, Destroyed),
                 core::mem::replace(&mut other.0, Destroyed),
@@ -131,9 +168,9 @@ pub mod ffi {
                 (Destroyed, _) | (_, Destroyed) => Err(icu_provider::DataError::custom(
                     "This provider has been destroyed",
                 ))?,
-                (Empty, Empty) => ICU4XDataProvider(ICU4XDataProviderInner::Empty),
+                (Empty, Empty) => ICU4XDataProvider(ICU4XDataProviderInner::Empty, id),
                 #[cfg(any(feature = "buffer_provider", feature = "any_provider"))]
-                (Empty, b) | (b, Empty) => ICU4XDataProvider(b),
+                (Empty, b) | (b, Empty) => ICU4XDataProvider(b, id),
                 #[cfg(feature = "any_provider")]
                 (Any(a), Any(b)) => {
                     convert_any_provider(icu_provider_adapters::fork::ForkByKeyProvider::new(a, b))
@@ -149,6 +186,7 @@ pub mod ffi {
                     ))?
                 }
             };
+            self.1 = id;
             Ok(())
         }
 
@@ -160,6 +198,9 @@ pub mod ffi {
         pub fn fork_by_locale(&mut self, other: &mut ICU4XDataProvider) -> Result<(), ICU4XError> {
             #[allow(unused_imports)]
             use ICU4XDataProviderInner::*;
+            // See `fork_by_key` for why this handle's cache identity is
+            // preserved across the reassignment below.
+            let id = self.1;
             *self = match (
                 core::mem::replace(&mut self.0, Destroyed),
                 core::mem::replace(&mut other.0, Destroyed),
@@ -167,9 +208,9 @@ pub mod ffi {
                 (Destroyed, _) | (_, Destroyed) => Err(icu_provider::DataError::custom(
                     "This provider has been destroyed",
                 ))?,
-                (Empty, Empty) => ICU4XDataProvider(ICU4XDataProviderInner::Empty),
+                (Empty, Empty) => ICU4XDataProvider(ICU4XDataProviderInner::Empty, id),
                 #[cfg(any(feature = "buffer_provider", feature = "any_provider"))]
-                (Empty, b) | (b, Empty) => ICU4XDataProvider(b),
+                (Empty, b) | (b, Empty) => ICU4XDataProvider(b, id),
                 #[cfg(feature = "any_provider")]
                 (Any(a), Any(b)) => convert_any_provider(
                     icu_provider_adapters::fork::ForkByErrorProvider::new_with_predicate(
@@ -193,6 +234,7 @@ pub mod ffi {
                     ))?
                 }
             };
+            self.1 = id;
             Ok(())
         }
 
@@ -210,6 +252,9 @@ pub mod ffi {
         )]
         pub fn enable_locale_fallback(&mut self) -> Result<(), ICU4XError> {
             use ICU4XDataProviderInner::*;
+            // See `fork_by_key` for why this handle's cache identity is
+            // preserved across the reassignment below.
+            let id = self.1;
             *self = match core::mem::replace(&mut self.0, Destroyed) {
                 Destroyed => Err(icu_provider::DataError::custom(
                     "This provider has been destroyed",
@@ -224,6 +269,7 @@ pub mod ffi {
                     LocaleFallbackProvider::try_new_with_buffer_provider(inner)?,
                 ),
             };
+            self.1 = id;
             Ok(())
         }
 
@@ -242,6 +288,9 @@ pub mod ffi {
             fallbacker: &ICU4XLocaleFallbacker,
         ) -> Result<(), ICU4XError> {
             use ICU4XDataProviderInner::*;
+            // See `fork_by_key` for why this handle's cache identity is
+            // preserved across the reassignment below.
+            let id = self.1;
             *self = match core::mem::replace(&mut self.0, Destroyed) {
                 Destroyed => Err(icu_provider::DataError::custom(
                     "This provider has been destroyed",
@@ -257,9 +306,34 @@ pub mod ffi {
                     LocaleFallbackProvider::new_with_fallbacker(inner, fallbacker.0.clone()),
                 ),
             };
+            self.1 = id;
             Ok(())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::ICU4XDataProvider;
+
+        #[test]
+        fn cache_key_is_never_reused_across_instances() {
+            let a = ICU4XDataProvider::create_empty();
+            let b = ICU4XDataProvider::create_empty();
+            assert_ne!(
+                a.cache_key(),
+                b.cache_key(),
+                "two distinct providers must never share a cache key, \
+                 even when (as here) they may share the same address once \
+                 one of them is dropped"
+            );
+        }
+
+        #[test]
+        fn cache_key_is_stable_for_the_same_instance() {
+            let a = ICU4XDataProvider::create_empty();
+            assert_eq!(a.cache_key(), a.cache_key());
+        }
+    }
 }
 
 macro_rules! load {